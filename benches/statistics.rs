@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use dag_statistics::generator::{fixture, fixture_database};
+use dag_statistics::DirectedAcyclicGraph;
+
+/// Node counts of the benchmarked fixtures
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Paths enumerated per iteration, the full enumeration is exponential
+const PATHS: usize = 1_000;
+
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for nodes in SIZES {
+        let database = fixture_database(nodes);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(nodes),
+            &database,
+            |b, database| {
+                b.iter(|| DirectedAcyclicGraph::from_read(black_box(database.as_slice())).unwrap())
+            },
+        );
+    }
+    group.finish();
+}
+
+fn depths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("avg_depth");
+    for nodes in SIZES {
+        let dag = fixture(nodes);
+        group.bench_with_input(BenchmarkId::from_parameter(nodes), &dag, |b, dag| {
+            b.iter(|| black_box(dag).avg_depth())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("max_depth");
+    for nodes in SIZES {
+        let dag = fixture(nodes);
+        group.bench_with_input(BenchmarkId::from_parameter(nodes), &dag, |b, dag| {
+            b.iter(|| black_box(dag).max_depth())
+        });
+    }
+    group.finish();
+}
+
+fn paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("paths_to_origin");
+    for nodes in SIZES {
+        let dag = fixture(nodes);
+        group.bench_with_input(BenchmarkId::from_parameter(nodes), &dag, |b, dag| {
+            b.iter(|| black_box(dag).paths_to_origin(nodes).take(PATHS).count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parsing, depths, paths);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+//! Feeds arbitrary bytes to the CSV edge lists parser
+//! Malformed input must fail with a `DagParseError`, never panic
+
+#![no_main]
+
+use dag_statistics::DirectedAcyclicGraph;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(dag) = DirectedAcyclicGraph::from_edge_list(data, ',') {
+        let _ = dag.statistics();
+    }
+});
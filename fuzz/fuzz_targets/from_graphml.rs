@@ -0,0 +1,15 @@
+//! Feeds arbitrary bytes to the GraphML parser
+//! Malformed input must fail with a `DagParseError`, never panic
+//! Inputs that once crashed are kept in `fuzz/regressions/from_graphml`, replay them with
+//! `cargo fuzz run from_graphml fuzz/regressions/from_graphml -- -runs=0`
+
+#![no_main]
+
+use dag_statistics::DirectedAcyclicGraph;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(dag) = DirectedAcyclicGraph::from_graphml(data) {
+        let _ = dag.statistics();
+    }
+});
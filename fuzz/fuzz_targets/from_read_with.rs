@@ -0,0 +1,26 @@
+//! Feeds arbitrary bytes to the database parser under every combination of parse options
+//! The first byte selects the options, the rest is the database
+
+#![no_main]
+
+use dag_statistics::{DirectedAcyclicGraph, DuplicateParents, ParseOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (&flags, database) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let duplicate_parents = match flags >> 2 & 3 {
+        0 => DuplicateParents::Reject,
+        1 => DuplicateParents::Count,
+        _ => DuplicateParents::Collapse,
+    };
+    let options = ParseOptions::new()
+        .relaxed(flags & 1 != 0)
+        .strict(flags & 2 != 0)
+        .duplicate_parents(duplicate_parents);
+    if let Ok((dag, _)) = DirectedAcyclicGraph::from_read_with(database, &options) {
+        let _ = dag.statistics();
+    }
+});
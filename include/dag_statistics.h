@@ -0,0 +1,54 @@
+/*
+ * C interface of dag-statistics, built with the `ffi` feature:
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * Graphs are opaque handles owned by the caller and released with dag_free.
+ * Edges point from a node to the node it references, node 1 is the origin.
+ * Statistics that are undefined for the graph are returned as NaN,
+ * every function accepts a null graph and returns 0, NaN or false for it.
+ */
+
+#ifndef DAG_STATISTICS_H
+#define DAG_STATISTICS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct DirectedAcyclicGraph DirectedAcyclicGraph;
+
+/* Creates an empty graph with node 1 as its origin */
+DirectedAcyclicGraph *dag_new(void);
+
+/* Reads a database file, returns NULL on failure, see dag_last_error */
+DirectedAcyclicGraph *dag_from_file(const char *path);
+
+/* Releases a graph, NULL is ignored */
+void dag_free(DirectedAcyclicGraph *dag);
+
+/* Message of the last failed call on this thread or NULL,
+ * valid until the next failing call on the same thread */
+const char *dag_last_error(void);
+
+/* Inserts an edge and its nodes, returns if the edge got actually added */
+bool dag_add_edge(DirectedAcyclicGraph *dag, size_t from, size_t to);
+
+size_t dag_node_count(const DirectedAcyclicGraph *dag);
+size_t dag_edge_count(const DirectedAcyclicGraph *dag);
+
+double dag_avg_depth(const DirectedAcyclicGraph *dag);
+size_t dag_max_depth(const DirectedAcyclicGraph *dag);
+double dag_avg_node_per_depth(const DirectedAcyclicGraph *dag);
+double dag_avg_ref(const DirectedAcyclicGraph *dag);
+double dag_avg_out_ref(const DirectedAcyclicGraph *dag);
+size_t dag_tip_count(const DirectedAcyclicGraph *dag);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DAG_STATISTICS_H */
@@ -0,0 +1,97 @@
+use std::io;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use crate::compression::is_compressed;
+use crate::database::{is_data_line, parse_header, DatabaseParser};
+use crate::{DagParseError, DirectedAcyclicGraph, ParseOptions};
+
+impl DirectedAcyclicGraph {
+    /// Same as `from_read` for asynchronous readers, parsing lines as they arrive
+    /// so a slow network stream never blocks a runtime worker thread
+    /// Compressed input is not decoded and fails with `InvalidData`
+    /// # Arguments
+    /// * `reader` - Anything that implements `tokio::io::AsyncRead`
+    pub async fn from_async_read(
+        reader: impl AsyncRead + Unpin,
+    ) -> Result<DirectedAcyclicGraph, DagParseError> {
+        let mut reader = BufReader::new(reader);
+        if is_compressed(reader.fill_buf().await?) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "compressed input is not supported by from_async_read, decompress it first",
+            )
+            .into());
+        }
+
+        let mut line = String::new();
+        let mut number = 0;
+        let found = next_line(&mut reader, &mut line, &mut number).await?;
+        let expected_lines = parse_header(found.then_some(&line), number)?;
+        let options = ParseOptions::default();
+        let mut parser = DatabaseParser::new(&options, expected_lines);
+        while next_line(&mut reader, &mut line, &mut number).await? {
+            parser.node_line(&line, number)?;
+        }
+
+        parser.finish().map(|(dag, _)| dag)
+    }
+}
+
+/// Advances to the next data line, returns false at the end of the input
+/// # Arguments
+/// * `reader` - The buffered input
+/// * `line` - Cleared and filled with the data line
+/// * `number` - 1-based number of the current line, advanced past skipped lines
+async fn next_line(
+    reader: &mut (impl AsyncBufReadExt + Unpin),
+    line: &mut String,
+    number: &mut usize,
+) -> io::Result<bool> {
+    loop {
+        line.clear();
+        if reader.read_line(line).await? == 0 {
+            return Ok(false);
+        }
+        *number += 1;
+        if is_data_line(line) {
+            return Ok(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DagParseError, DirectedAcyclicGraph};
+
+    /// Runs `future` to completion on a single threaded runtime
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_from_async_read() {
+        let database = "# comment\n3\n1 1\n\n1 2\n2 3\n";
+        let dag = block_on(DirectedAcyclicGraph::from_async_read(database.as_bytes())).unwrap();
+        assert_eq!(
+            dag,
+            DirectedAcyclicGraph::from_read(database.as_bytes()).unwrap()
+        );
+
+        assert!(matches!(
+            block_on(DirectedAcyclicGraph::from_async_read(
+                "2\n1 1\n1 x\n".as_bytes()
+            )),
+            Err(DagParseError::InvalidInteger { line: 3, .. })
+        ));
+        assert!(matches!(
+            block_on(DirectedAcyclicGraph::from_async_read(
+                &[0x1f, 0x8b, 0x08][..]
+            )),
+            Err(DagParseError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::hash::HashMap;
+use crate::{BigUint, DirectedAcyclicGraph};
+
+impl DirectedAcyclicGraph {
+    /// Nodes whose removal would disconnect at least one other node from the origins,
+    /// the non-origin nodes that dominate another node, sorted ascending
+    /// Returns `None` if the graph is cyclic
+    pub fn cut_nodes(&self) -> Option<Vec<usize>> {
+        let dominators = self.dominators()?;
+        let mut cut: Vec<usize> = self
+            .nodes()
+            .filter_map(|node| dominators.immediate_dominator(node))
+            .filter(|node| !self.is_origin(*node))
+            .collect();
+        cut.sort_unstable();
+        cut.dedup();
+        Some(cut)
+    }
+
+    /// Fraction of all paths from non-origin nodes to an origin that pass through `node`,
+    /// 1.0 for a node every path passes through and 0.0 for nodes without a path to an origin
+    /// Returns `None` if the node does not exist or the graph is cyclic
+    /// # Arguments
+    /// * `node` - Node id to score
+    pub fn bottleneck_score(&self, node: usize) -> Option<f64> {
+        self.bottleneck_scores()?.get(&node).copied()
+    }
+
+    /// `bottleneck_score` of every node, computed with exact path counts in two linear passes
+    /// Returns `None` if the graph is cyclic
+    pub fn bottleneck_scores(&self) -> Option<HashMap<usize, f64>> {
+        let order = self.topological_order()?;
+
+        // Paths from every node to an origin
+        let down = self.origin_path_counts(&order);
+
+        // Paths from every non-origin node to each node, children first,
+        // paths end at the first origin so they never pass through one
+        let mut up: HashMap<usize, BigUint> = HashMap::new();
+        for &node in &order {
+            let mut count: BigUint = self
+                .predecessors(node)
+                .iter()
+                .filter(|child| !self.is_origin(**child))
+                .map(|child| &up[child])
+                .sum();
+            if !self.is_origin(node) {
+                count += 1u8;
+            }
+            up.insert(node, count);
+        }
+
+        let total: BigUint = self
+            .nodes()
+            .filter(|node| !self.is_origin(*node))
+            .map(|node| &down[&node])
+            .sum();
+        let scores = self
+            .nodes()
+            .map(|node| (node, ratio(&(&up[&node] * &down[&node]), &total)))
+            .collect();
+        Some(scores)
+    }
+}
+
+/// `numerator / denominator` as a float even if both overflow it, 0.0 for a zero denominator
+/// # Arguments
+/// * `numerator` - Dividend
+/// * `denominator` - Divisor
+fn ratio(numerator: &BigUint, denominator: &BigUint) -> f64 {
+    // Drop the low bits so both fit in a u64, keeping 64 bits of precision
+    let shift = denominator.bits().saturating_sub(64);
+    let numerator = u64::try_from(numerator >> shift).unwrap_or(u64::MAX);
+    let denominator = u64::try_from(denominator >> shift).unwrap_or(u64::MAX);
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_cut_nodes_and_bottlenecks() {
+        let dag: DirectedAcyclicGraph = vec![(2, 1), (3, 1), (4, 2), (4, 3), (5, 4), (6, 5)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(dag.cut_nodes(), Some(vec![4, 5]));
+        // 2 and 3 have a path each, 4 has two, 5 and 6 two each through 4
+        assert_eq!(dag.bottleneck_score(1), Some(1.0));
+        assert_eq!(dag.bottleneck_score(4), Some(6.0 / 8.0));
+        assert_eq!(dag.bottleneck_score(2), Some(4.0 / 8.0));
+        assert_eq!(dag.bottleneck_score(6), Some(2.0 / 8.0));
+        assert_eq!(dag.bottleneck_score(7), None);
+    }
+}
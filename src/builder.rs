@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+
+use crate::{BuildError, DirectedAcyclicGraph};
+
+/// Collects nodes and edges and validates them once when the DAG is built
+/// Without any `origin` call node 1 is the origin
+#[derive(Debug, Clone, Default)]
+pub struct DagBuilder {
+    origins: Vec<usize>,
+    nodes: Vec<usize>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl DagBuilder {
+    /// Returns an empty builder
+    pub fn new() -> DagBuilder {
+        DagBuilder::default()
+    }
+
+    /// Returns an empty builder with room for the given number of nodes and edges
+    /// # Arguments
+    /// * `nodes` - Expected number of nodes
+    /// * `edges` - Expected number of edges
+    pub fn with_capacity(nodes: usize, edges: usize) -> DagBuilder {
+        DagBuilder {
+            origins: Vec::new(),
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+        }
+    }
+
+    /// Adds an origin, can be called repeatedly for several origins
+    /// # Arguments
+    /// * `origin` - Origin node id
+    pub fn origin(mut self, origin: usize) -> DagBuilder {
+        self.origins.push(origin);
+        self
+    }
+
+    /// Adds a node, which may stay without edges
+    /// # Arguments
+    /// * `node` - Node id to insert
+    pub fn node(mut self, node: usize) -> DagBuilder {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Adds an edge and its nodes, duplicates are ignored
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn edge(mut self, from: usize, to: usize) -> DagBuilder {
+        self.edges.push((from, to));
+        self
+    }
+
+    /// Builds the DAG
+    /// Returns an error if an edge references its own node or the edges form a cycle
+    pub fn build(self) -> Result<DirectedAcyclicGraph, BuildError> {
+        if let Some((node, _)) = self.edges.iter().find(|(from, to)| from == to) {
+            return Err(BuildError::SelfReference(*node));
+        }
+
+        let mut dag = if self.origins.is_empty() {
+            DirectedAcyclicGraph::new()
+        } else {
+            DirectedAcyclicGraph::with_origins(self.origins)
+        };
+        dag.nodes.reserve(self.nodes.len() + self.edges.len());
+        dag.neighbors.reserve(self.edges.len());
+        dag.predecessors.reserve(self.edges.len());
+
+        dag.nodes.extend(self.nodes);
+        dag.add_edges(self.edges);
+
+        if !dag.is_acyclic() {
+            let cycle = dag.validate().cycles.swap_remove(0);
+            return Err(BuildError::Cycle(cycle));
+        }
+        Ok(dag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuildError, DagBuilder};
+
+    #[test]
+    fn test_builder() {
+        let dag = DagBuilder::with_capacity(4, 3)
+            .origin(10)
+            .node(13)
+            .edge(11, 10)
+            .edge(12, 11)
+            .edge(12, 10)
+            .build()
+            .unwrap();
+        assert_eq!(dag.origins(), &[10]);
+        assert_eq!(dag.node_count(), 4);
+        assert_eq!(dag.edge_count(), 3);
+
+        let cyclic = DagBuilder::new().edge(2, 1).edge(3, 2).edge(2, 3).build();
+        assert!(matches!(cyclic, Err(BuildError::Cycle(cycle)) if cycle.len() == 2));
+        assert_eq!(
+            DagBuilder::new().edge(2, 2).build().unwrap_err(),
+            BuildError::SelfReference(2)
+        );
+    }
+}
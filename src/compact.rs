@@ -0,0 +1,268 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::memory::vec_bytes;
+use crate::{CompactDagError, DagView, DirectedAcyclicGraph, MemoryReport};
+
+/// Immutable DAG stored as compressed sparse rows with 32 bit node positions
+/// Nodes are kept in topological order, so every parent comes after the nodes referencing it,
+/// and the adjacency of all nodes is packed in two arrays instead of one list per node
+/// Build it with `DirectedAcyclicGraph::to_compact` and query it through `DagView`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactDag {
+    /// Node id at every position
+    ids: Vec<u32>,
+    /// Positions sorted by node id, for looking up the position of an id
+    by_id: Vec<u32>,
+    /// Range of the parents of position `i` in `parents` is `parent_offsets[i]..parent_offsets[i + 1]`
+    parent_offsets: Vec<u32>,
+    /// Parent positions of every node
+    parents: Vec<u32>,
+    /// Range of the children of position `i` in `children`, like `parent_offsets`
+    child_offsets: Vec<u32>,
+    /// Child positions of every node
+    children: Vec<u32>,
+    /// Origin ids that are nodes, sorted ascending
+    origins: Vec<u32>,
+    /// In and out references of every position, only kept if some edges repeat
+    references: Option<(Vec<u32>, Vec<u32>)>,
+    /// Number of references, the edges counted by their multiplicity
+    reference_count: usize,
+}
+
+impl CompactDag {
+    /// Position of a node id, `None` for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to look up
+    fn position(&self, node: usize) -> Option<usize> {
+        let node = u32::try_from(node).ok()?;
+        self.by_id
+            .binary_search_by_key(&node, |position| self.ids[*position as usize])
+            .ok()
+            .map(|index| self.by_id[index] as usize)
+    }
+
+    /// Estimates the heap memory held by the graph, see `DirectedAcyclicGraph::memory_usage`
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: vec_bytes(&self.ids) + vec_bytes(&self.by_id) + vec_bytes(&self.origins),
+            edges: vec_bytes(&self.parent_offsets)
+                + vec_bytes(&self.parents)
+                + vec_bytes(&self.child_offsets)
+                + vec_bytes(&self.children),
+            metadata: self
+                .references
+                .as_ref()
+                .map_or(0, |(in_references, out_references)| {
+                    vec_bytes(in_references) + vec_bytes(out_references)
+                }),
+            indexes: 0,
+        }
+    }
+
+    /// Node ids of a packed adjacency range
+    /// # Arguments
+    /// * `offsets` - `parent_offsets` or `child_offsets`
+    /// * `positions` - `parents` or `children`
+    /// * `node` - Node id whose range is read
+    fn adjacent<'a>(
+        &'a self,
+        offsets: &'a [u32],
+        positions: &'a [u32],
+        node: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let range = self.position(node).map_or(0..0, |position| {
+            offsets[position] as usize..offsets[position + 1] as usize
+        });
+        positions[range]
+            .iter()
+            .map(move |position| self.ids[*position as usize] as usize)
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Packs the graph into a `CompactDag`, dropping weights and attributes
+    /// Fails if the graph is cyclic or does not fit 32 bit ids and offsets
+    pub fn to_compact(&self) -> Result<CompactDag, CompactDagError> {
+        let order = self.topological_order().ok_or(CompactDagError::Cyclic)?;
+        let too_large = |_| CompactDagError::TooLarge;
+        let mut ids = Vec::with_capacity(order.len());
+        for &node in &order {
+            ids.push(u32::try_from(node).map_err(|_| CompactDagError::IdOutOfRange(node))?);
+        }
+        u32::try_from(self.edge_count()).map_err(too_large)?;
+
+        let mut by_id: Vec<u32> = (0..ids.len() as u32).collect();
+        by_id.sort_unstable_by_key(|position| ids[*position as usize]);
+        let position = |node: &usize| {
+            let node = *node as u32;
+            by_id[by_id
+                .binary_search_by_key(&node, |position| ids[*position as usize])
+                .expect("adjacent nodes are part of the graph")]
+        };
+
+        // Packs the parents if `forward` is set, the children otherwise
+        let pack = |forward: bool| {
+            let mut offsets = Vec::with_capacity(order.len() + 1);
+            let mut positions = Vec::with_capacity(self.edge_count());
+            offsets.push(0);
+            for &node in &order {
+                let adjacent = if forward {
+                    self.neighbors(node)
+                } else {
+                    self.predecessors(node)
+                };
+                let start = positions.len();
+                positions.extend(adjacent.iter().map(position));
+                positions[start..].sort_unstable();
+                offsets.push(positions.len() as u32);
+            }
+            (offsets, positions)
+        };
+        let (parent_offsets, parents) = pack(true);
+        let (child_offsets, children) = pack(false);
+
+        let references = if self.reference_count() == self.edge_count() {
+            None
+        } else {
+            let count = |references: usize| u32::try_from(references).map_err(too_large);
+            let mut in_references = Vec::with_capacity(order.len());
+            let mut out_references = Vec::with_capacity(order.len());
+            for &node in &order {
+                in_references.push(count(self.in_references(node))?);
+                out_references.push(count(self.out_references(node))?);
+            }
+            Some((in_references, out_references))
+        };
+
+        Ok(CompactDag {
+            origins: self
+                .origins()
+                .iter()
+                .filter(|origin| self.contains_node(**origin))
+                .map(|origin| *origin as u32)
+                .collect(),
+            ids,
+            by_id,
+            parent_offsets,
+            parents,
+            child_offsets,
+            children,
+            references,
+            reference_count: self.reference_count(),
+        })
+    }
+}
+
+impl DagView for CompactDag {
+    fn node_count(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.parents.len()
+    }
+
+    fn node_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ids.iter().map(|id| *id as usize)
+    }
+
+    fn parent_ids(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacent(&self.parent_offsets, &self.parents, node)
+    }
+
+    fn child_ids(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacent(&self.child_offsets, &self.children, node)
+    }
+
+    fn origin_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.origins.iter().map(|origin| *origin as usize)
+    }
+
+    fn is_origin(&self, node: usize) -> bool {
+        u32::try_from(node).is_ok_and(|node| self.origins.binary_search(&node).is_ok())
+    }
+
+    fn in_references(&self, node: usize) -> usize {
+        match (&self.references, self.position(node)) {
+            (Some((in_references, _)), Some(position)) => in_references[position] as usize,
+            (None, Some(position)) => {
+                (self.child_offsets[position + 1] - self.child_offsets[position]) as usize
+            }
+            (_, None) => 0,
+        }
+    }
+
+    fn out_references(&self, node: usize) -> usize {
+        match (&self.references, self.position(node)) {
+            (Some((_, out_references)), Some(position)) => out_references[position] as usize,
+            (None, Some(position)) => {
+                (self.parent_offsets[position + 1] - self.parent_offsets[position]) as usize
+            }
+            (_, None) => 0,
+        }
+    }
+
+    fn reference_count(&self) -> usize {
+        self.reference_count
+    }
+
+    /// The stored node order, always `Some` as a `CompactDag` is acyclic
+    fn topological_order(&self) -> Option<Vec<usize>> {
+        Some(self.node_ids().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use crate::{sample_dag, CompactDagError, DagView, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_compact_dag_matches_graph() {
+        let mut dag = sample_dag();
+        dag.add_edge_with_multiplicity(6, 4, 3);
+        let compact = dag.to_compact().unwrap();
+
+        assert_eq!(DagView::statistics(&compact), dag.statistics());
+        assert_eq!(DagView::max_depth(&compact), dag.max_depth());
+        assert_eq!(compact.level_sizes(), dag.level_sizes());
+        for node in dag.nodes() {
+            let mut parents: Vec<usize> = compact.parent_ids(node).collect();
+            let mut expected = dag.neighbors(node).to_vec();
+            parents.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(parents, expected);
+            assert_eq!(compact.child_ids(node).count(), dag.in_degree(node));
+            assert_eq!(
+                DagView::in_references(&compact, node),
+                dag.in_references(node)
+            );
+        }
+        assert_eq!(compact.parent_ids(42).count(), 0);
+
+        let order = DagView::topological_order(&compact).unwrap();
+        for (from, to) in dag.edges() {
+            let from = order.iter().position(|node| *node == from);
+            let to = order.iter().position(|node| *node == to);
+            assert!(from < to);
+        }
+    }
+
+    #[test]
+    fn test_compact_dag_errors() {
+        let mut cyclic = DirectedAcyclicGraph::new();
+        cyclic.add_edge(2, 1);
+        cyclic.add_edge(3, 2);
+        cyclic.add_edge(2, 3);
+        assert_eq!(cyclic.to_compact(), Err(CompactDagError::Cyclic));
+
+        // Ids past u32 only exist where usize is wider than 32 bits
+        if let Ok(id) = usize::try_from(1u64 << 40) {
+            let mut huge = DirectedAcyclicGraph::new();
+            huge.add_edge(id, 1);
+            assert_eq!(huge.to_compact(), Err(CompactDagError::IdOutOfRange(id)));
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
+use crate::hash::HashSet;
+use crate::DirectedAcyclicGraph;
+
+impl DirectedAcyclicGraph {
+    /// Partition of the nodes into weakly connected components, ignoring edge directions
+    /// Each component is sorted and the components are ordered by their smallest node
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut nodes: Vec<usize> = self.nodes().collect();
+        nodes.sort_unstable();
+
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for node in nodes {
+            if !visited.insert(node) {
+                continue;
+            }
+
+            let mut component = vec![node];
+            let mut queue = VecDeque::new();
+            queue.push_back(node);
+            while let Some(current) = queue.pop_front() {
+                let adjacent = self
+                    .neighbors(current)
+                    .iter()
+                    .chain(self.predecessors(current));
+                for &next in adjacent {
+                    if visited.insert(next) {
+                        component.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Returns if every node is connected to every other node when ignoring edge directions
+    /// An empty DAG counts as connected
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_connected_components() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 1);
+        assert!(dag.is_connected());
+
+        dag.add_edge(5, 4);
+        dag.add_edge(6, 5);
+        assert_eq!(dag.connected_components(), [vec![1, 2, 3], vec![4, 5, 6]]);
+        assert!(!dag.is_connected());
+    }
+}
@@ -0,0 +1,125 @@
+use std::io::{self, Cursor, Read};
+
+/// Magic number every gzip stream starts with
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Magic number every zstd frame starts with
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Wraps `reader` in a decoder if its content starts with a gzip or zstd magic number,
+/// otherwise the content is passed through unchanged
+/// Decoding gzip needs the `flate2` feature and zstd the `zstd` feature,
+/// compressed input without the feature fails with `InvalidData`
+/// # Arguments
+/// * `reader` - Anything that implements `Read`
+pub fn decompress<'a, R: Read + 'a>(mut reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    // Only the magic number is read ahead, then put back in front of the rest
+    let mut head = [0; 4];
+    let mut len = 0;
+    while len < head.len() {
+        match reader.read(&mut head[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    let head = &head[..len];
+    let reader = Cursor::new(head.to_vec()).chain(reader);
+
+    if head.starts_with(&GZIP_MAGIC) {
+        #[cfg(feature = "flate2")]
+        return Ok(Box::new(flate2::read::MultiGzDecoder::new(reader)));
+        #[cfg(not(feature = "flate2"))]
+        return Err(missing_feature("gzip", "flate2"));
+    }
+    if head.starts_with(&ZSTD_MAGIC) {
+        #[cfg(feature = "zstd")]
+        return Ok(Box::new(zstd::stream::read::Decoder::new(reader)?));
+        #[cfg(not(feature = "zstd"))]
+        return Err(missing_feature("zstd", "zstd"));
+    }
+
+    Ok(Box::new(reader))
+}
+
+/// Returns if `head`, the start of an input, carries a gzip or zstd magic number
+/// # Arguments
+/// * `head` - The first bytes of the input
+#[cfg(feature = "tokio")]
+pub(crate) fn is_compressed(head: &[u8]) -> bool {
+    head.starts_with(&GZIP_MAGIC) || head.starts_with(&ZSTD_MAGIC)
+}
+
+/// Error for compressed input whose decoder is not compiled in
+/// # Arguments
+/// * `compression` - Name of the detected compression
+/// * `feature` - Cargo feature enabling its decoder
+#[allow(dead_code)]
+fn missing_feature(compression: &str, feature: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "{} compressed input requires building with the `{}` feature",
+            compression, feature
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::decompress;
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_plain_input_passes_through() {
+        let mut content = String::new();
+        decompress("3\n1 1\n".as_bytes())
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "3\n1 1\n");
+
+        let mut content = Vec::new();
+        decompress(&[0x1fu8][..])
+            .unwrap()
+            .read_to_end(&mut content)
+            .unwrap();
+        assert_eq!(content, [0x1f]);
+    }
+
+    #[test]
+    fn test_compressed_database() {
+        let expected = sample_dag();
+        assert_eq!(expected.node_count(), 6);
+
+        #[cfg(feature = "flate2")]
+        {
+            use std::io::Write;
+
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder
+                .write_all(crate::SAMPLE_DATABASE.as_bytes())
+                .unwrap();
+            let gzip = encoder.finish().unwrap();
+            assert_eq!(
+                DirectedAcyclicGraph::from_read(gzip.as_slice()).unwrap(),
+                expected
+            );
+        }
+
+        #[cfg(feature = "zstd")]
+        {
+            let zstd = zstd::encode_all(crate::SAMPLE_DATABASE.as_bytes(), 0).unwrap();
+            assert_eq!(
+                DirectedAcyclicGraph::from_read(zstd.as_slice()).unwrap(),
+                expected
+            );
+        }
+
+        #[cfg(not(feature = "flate2"))]
+        assert!(DirectedAcyclicGraph::from_read(&[0x1f, 0x8b, 0, 0][..]).is_err());
+    }
+}
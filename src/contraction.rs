@@ -0,0 +1,262 @@
+use alloc::{string::ToString, vec, vec::Vec};
+
+use crate::hash::{HashMap, HashSet};
+use crate::{ContractionError, DirectedAcyclicGraph};
+
+/// Combined weight and multiplicity of the parallel edges merged by a contraction
+#[derive(Default)]
+struct MergedEdge {
+    weight: Option<f64>,
+    multiplicity: usize,
+}
+
+impl DirectedAcyclicGraph {
+    /// Merges `from` into its parent `to`, keeping the id `to` and every other edge of both
+    /// Returns if the edge existed, or an error if another path from `from` to `to` would become a cycle
+    /// # Arguments
+    /// * `from` - Start node id of the contracted edge
+    /// * `to` - Destination node id of the contracted edge, the id of the merged node
+    pub fn contract_edge(&mut self, from: usize, to: usize) -> Result<bool, ContractionError> {
+        if !self.contains_edge(from, to) {
+            return Ok(false);
+        }
+
+        let nodes = [from, to].iter().copied().collect();
+        self.contract_nodes(&nodes, to).map(|_| true)
+    }
+
+    /// Merges `nodes` into a single node `new_id` that keeps every edge to the rest of the graph
+    /// Parallel edges created by the merge add up their multiplicities and keep the heaviest weight,
+    /// the merged node is an origin if any of `nodes` was and keeps the attributes of `new_id`
+    /// Node ids missing from the DAG are ignored
+    /// Fails without changes if `new_id` belongs to another node or the merge would close a cycle
+    /// # Arguments
+    /// * `nodes` - Node ids to merge
+    /// * `new_id` - Id of the merged node, may be one of `nodes`
+    pub fn contract_nodes(
+        &mut self,
+        nodes: &HashSet<usize>,
+        new_id: usize,
+    ) -> Result<(), ContractionError> {
+        let members: HashSet<usize> = nodes
+            .iter()
+            .copied()
+            .filter(|node| self.nodes.contains(node))
+            .collect();
+        if members.is_empty() {
+            return Ok(());
+        }
+        if self.nodes.contains(&new_id) && !members.contains(&new_id) {
+            return Err(ContractionError::IdInUse(new_id));
+        }
+        if let Some(node) = self.detour(&members) {
+            return Err(ContractionError::WouldCreateCycle(node));
+        }
+
+        let mut outgoing: HashMap<usize, MergedEdge> = HashMap::new();
+        let mut incoming: HashMap<usize, MergedEdge> = HashMap::new();
+        for &member in &members {
+            for &to in self.neighbors(member) {
+                if !members.contains(&to) {
+                    self.merge_edge(outgoing.entry(to).or_default(), member, to);
+                }
+            }
+            for &from in self.predecessors(member) {
+                if !members.contains(&from) {
+                    self.merge_edge(incoming.entry(from).or_default(), from, member);
+                }
+            }
+        }
+
+        let attributes = self.attributes.remove(&new_id);
+        for &member in &members {
+            let mut edges: Vec<(usize, usize)> = self
+                .neighbors(member)
+                .iter()
+                .map(|&to| (member, to))
+                .collect();
+            edges.extend(self.predecessors(member).iter().map(|&from| (from, member)));
+            for (from, to) in edges {
+                self.unlink(from, to);
+            }
+            self.nodes.remove(&member);
+            self.attributes.remove(&member);
+        }
+
+        self.nodes.insert(new_id);
+        if let Some(attributes) = attributes {
+            self.attributes.insert(new_id, attributes);
+        }
+        let edges = outgoing
+            .into_iter()
+            .map(|(to, edge)| ((new_id, to), edge))
+            .chain(
+                incoming
+                    .into_iter()
+                    .map(|(from, edge)| ((from, new_id), edge)),
+            );
+        for ((from, to), edge) in edges {
+            self.insert_edge(from, to);
+            if let Some(weight) = edge.weight {
+                self.weights.insert((from, to), weight);
+            }
+            if edge.multiplicity > 1 {
+                self.multiplicities.insert((from, to), edge.multiplicity);
+            }
+        }
+
+        if self.origins.iter().any(|origin| members.contains(origin)) {
+            self.origins.retain(|origin| !members.contains(origin));
+            self.origins.push(new_id);
+            self.origins.sort_unstable();
+        }
+        self.reachability = None;
+        Ok(())
+    }
+
+    /// Summary graph with one node per level, a compact fingerprint of the overall shape
+    /// Level `d` holds the nodes whose longest path to an origin has `d` edges, so every edge
+    /// leads to a shallower level and the summary stays acyclic, its origin is level 0
+    /// Each level node has a `size` attribute counting its nodes and the multiplicity of
+    /// each summary edge counts the references between the two levels
+    /// Nodes without a path to an origin are left out
+    /// Returns `None` if the graph is cyclic
+    pub fn level_condensation(&self) -> Option<DirectedAcyclicGraph> {
+        let order = self.topological_order()?;
+        let depths = self.max_depths(&order);
+
+        let mut summary = DirectedAcyclicGraph::with_origin(0);
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        let mut volumes: HashMap<(usize, usize), usize> = HashMap::new();
+        for (&node, &depth) in &depths {
+            *sizes.entry(depth).or_insert(0) += 1;
+            for &neighbor in self.neighbors(node) {
+                // Only edges leaving an origin can stay on the same level, they are dropped
+                match depths.get(&neighbor) {
+                    Some(&level) if level < depth => {
+                        *volumes.entry((depth, level)).or_insert(0) +=
+                            self.edge_multiplicity(node, neighbor).unwrap_or(1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for ((from, to), volume) in volumes {
+            summary.add_edge_with_multiplicity(from, to, volume);
+        }
+        for (level, size) in sizes {
+            summary.add_node(level);
+            summary.set_node_attr(level, "size", size.to_string());
+        }
+        Some(summary)
+    }
+
+    /// Adds the weight and multiplicity of an edge to a merged edge
+    /// # Arguments
+    /// * `merged` - Merged edge to update
+    /// * `from` - Start node id of the added edge
+    /// * `to` - Destination node id of the added edge
+    fn merge_edge(&self, merged: &mut MergedEdge, from: usize, to: usize) {
+        if let Some(&weight) = self.weights.get(&(from, to)) {
+            merged.weight = Some(merged.weight.map_or(weight, |current| current.max(weight)));
+        }
+        merged.multiplicity += self.edge_multiplicity(from, to).unwrap_or(1);
+    }
+
+    /// First node outside `members` on a path that leaves the set and enters it again,
+    /// such a path would become a cycle once the set is merged into one node
+    /// # Arguments
+    /// * `members` - Node ids to merge
+    fn detour(&self, members: &HashSet<usize>) -> Option<usize> {
+        let mut visited = HashSet::new();
+        for &member in members {
+            for &start in self.neighbors(member) {
+                if members.contains(&start) || visited.contains(&start) {
+                    continue;
+                }
+
+                visited.insert(start);
+                let mut stack = vec![start];
+                while let Some(node) = stack.pop() {
+                    for &neighbor in self.neighbors(node) {
+                        if members.contains(&neighbor) {
+                            return Some(start);
+                        }
+                        if visited.insert(neighbor) {
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::HashSet;
+
+    use crate::{sample_dag, ContractionError, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_contract_edge() {
+        let mut dag: DirectedAcyclicGraph = vec![(2, 1), (3, 2), (3, 1), (4, 2), (5, 3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            dag.contract_edge(3, 1),
+            Err(ContractionError::WouldCreateCycle(2))
+        );
+        assert_eq!(dag.contract_edge(4, 1), Ok(false));
+        assert_eq!(dag.contract_edge(3, 2), Ok(true));
+
+        assert!(!dag.contains_node(3));
+        assert_eq!(dag.edge_count(), 3);
+        assert!(dag.contains_edge(5, 2) && dag.contains_edge(4, 2) && dag.contains_edge(2, 1));
+        assert_eq!(dag.edge_multiplicity(2, 1), Some(2));
+        assert!(dag.is_acyclic());
+    }
+
+    #[test]
+    fn test_contract_nodes() {
+        let mut dag: DirectedAcyclicGraph = vec![(2, 1), (3, 1), (4, 2), (4, 3), (5, 4)]
+            .into_iter()
+            .collect();
+        dag.add_weighted_edge(4, 3, 2.5);
+
+        let level: HashSet<usize> = vec![2, 3].into_iter().collect();
+        assert_eq!(
+            dag.contract_nodes(&level, 5),
+            Err(ContractionError::IdInUse(5))
+        );
+        dag.contract_nodes(&level, 10).unwrap();
+        assert_eq!(dag.node_count(), 4);
+        assert_eq!(dag.edge_weight(4, 10), Some(2.5));
+        assert_eq!(dag.edge_multiplicity(10, 1), Some(2));
+        assert_eq!(dag.max_depth(), 4);
+
+        let origin: HashSet<usize> = vec![1, 10].into_iter().collect();
+        dag.contract_nodes(&origin, 0).unwrap();
+        assert_eq!(dag.origins(), &[0]);
+        assert_eq!(dag.neighbors(4), &[0]);
+    }
+
+    #[test]
+    fn test_level_condensation() {
+        let dag = sample_dag();
+        let summary = dag.level_condensation().unwrap();
+
+        assert_eq!(summary.origins(), &[0]);
+        assert_eq!(summary.node_count(), 5);
+        assert_eq!(summary.node_attr(0, "size"), Some("1"));
+        assert_eq!(summary.node_attr(2, "size"), Some("2"));
+        assert_eq!(summary.edge_multiplicity(2, 1), Some(2));
+        assert_eq!(summary.edge_multiplicity(2, 0), Some(1));
+        assert_eq!(summary.reference_count(), dag.reference_count());
+        assert_eq!(summary.max_depth(), dag.max_depth());
+    }
+}
@@ -0,0 +1,640 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use crate::{decompress, DagParseError, DatabaseWriteError, DirectedAcyclicGraph, HeaderMismatch};
+
+/// Parsing progress reported by `DirectedAcyclicGraph::from_read_streaming`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of node lines parsed so far
+    pub lines: usize,
+    /// Number of bytes consumed so far, including the header, counted after decompression
+    pub bytes: u64,
+    /// Number of node lines announced by the header
+    pub expected_lines: usize,
+}
+
+/// Headers announcing more nodes than this are not trusted for preallocation
+const MAX_RESERVED_NODES: usize = 1 << 24;
+
+/// Options of `DirectedAcyclicGraph::from_read_with`, the defaults parse like `from_read`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    relaxed: bool,
+    strict: bool,
+    duplicate_parents: DuplicateParents,
+}
+
+/// What to do with a parent listed more than once on the same node line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateParents {
+    /// Fail the parse, note that `to_database` writes single parents twice
+    Reject,
+    /// Keep a single edge, the way single parents are written
+    #[default]
+    Collapse,
+    /// Keep a single edge and count the repetitions, see `edge_multiplicity`
+    Count,
+}
+
+/// Problem tolerated by a lenient parse, see `ParseOptions::strict`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The header announces a different number of node lines than the input has
+    HeaderMismatch(HeaderMismatch),
+    /// Parent ids that have no node line of their own, sorted ascending
+    PhantomNodes(Vec<usize>),
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::HeaderMismatch(HeaderMismatch { expected, actual }) => write!(
+                f,
+                "header announces {} node lines, found {}",
+                expected, actual
+            ),
+            ParseWarning::PhantomNodes(nodes) => {
+                write!(f, "parents {:?} have no node line of their own", nodes)
+            }
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Returns the default options
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// Accepts any number of whitespace separated parents per node line instead of exactly two,
+    /// at least one parent is still required
+    /// # Arguments
+    /// * `relaxed` - Whether to accept any parent count
+    pub fn relaxed(mut self, relaxed: bool) -> ParseOptions {
+        self.relaxed = relaxed;
+        self
+    }
+
+    /// Fails on problems a lenient parse only warns about, such as a header
+    /// announcing a different number of node lines than the input has
+    /// Also rejects parents with a greater id than their node or outside of 1 through N + 1,
+    /// which a lenient parse accepts and reports as phantom nodes if they never get a line
+    /// # Arguments
+    /// * `strict` - Whether to fail instead of warning
+    pub fn strict(mut self, strict: bool) -> ParseOptions {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets what to do with a parent listed more than once on the same node line
+    /// # Arguments
+    /// * `policy` - Reject, collapse (the default) or count duplicates
+    pub fn duplicate_parents(mut self, policy: DuplicateParents) -> ParseOptions {
+        self.duplicate_parents = policy;
+        self
+    }
+
+    /// What to do with a parent listed more than once on the same node line
+    pub(crate) fn duplicate_parent_policy(&self) -> DuplicateParents {
+        self.duplicate_parents
+    }
+
+    /// Parses the parents of a node line
+    /// # Arguments
+    /// * `line` - The node line
+    /// * `number` - Line number used for error reporting
+    /// * `parents` - Cleared and filled with the parent ids
+    pub(crate) fn parse_parents(
+        &self,
+        line: &str,
+        number: usize,
+        parents: &mut Vec<usize>,
+    ) -> Result<(), DagParseError> {
+        parents.clear();
+        let mut fields = line.split_whitespace();
+        if self.relaxed {
+            for field in fields {
+                parents.push(parse_field(Some(field), number, "parent")?);
+            }
+            if parents.is_empty() {
+                return Err(DagParseError::MissingField {
+                    line: number,
+                    field: "parent",
+                });
+            }
+        } else {
+            parents.push(parse_field(fields.next(), number, "left parent")?);
+            parents.push(parse_field(fields.next(), number, "right parent")?);
+        }
+
+        Ok(())
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Creates a new Directed Acyclic Graph from anything that implements `Read`
+    /// The database structure is as follows
+    /// Line 1: N, the number of nodes in the database
+    /// Lines 2 through N + 1: the node data, where each node consists of the ids of its left and right parents
+    /// Node id 1 is the unique origin of all nodes
+    /// The id of each node in the database is its line number, not counting blank lines
+    /// and `#` comments, which are skipped anywhere in the input
+    /// Gzip and zstd compressed input is decompressed transparently, see `decompress`
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    pub fn from_read(reader: impl Read) -> Result<DirectedAcyclicGraph, DagParseError> {
+        DirectedAcyclicGraph::from_read_streaming(reader, |_| {})
+    }
+
+    /// Same as `from_read` with non-default parsing options
+    /// Also returns the problems tolerated by a lenient parse
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    /// * `options` - How to parse the database
+    pub fn from_read_with(
+        reader: impl Read,
+        options: &ParseOptions,
+    ) -> Result<(DirectedAcyclicGraph, Vec<ParseWarning>), DagParseError> {
+        DirectedAcyclicGraph::read_database(reader, options, |_| {})
+    }
+
+    /// Reads a database file as `from_read` does, gzip and zstd files included
+    /// # Arguments
+    /// * `path` - Path of the database file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<DirectedAcyclicGraph, DagParseError> {
+        DirectedAcyclicGraph::from_read(File::open(path)?)
+    }
+
+    /// Same as `from_read` but calls `progress` after every parsed node line
+    /// Lines are parsed one at a time into a reused buffer and inserted right away,
+    /// so memory use is bounded by the graph itself rather than the input size
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    /// * `progress` - Called with the progress so far after every node line
+    pub fn from_read_streaming(
+        reader: impl Read,
+        progress: impl FnMut(Progress),
+    ) -> Result<DirectedAcyclicGraph, DagParseError> {
+        DirectedAcyclicGraph::read_database(reader, &ParseOptions::default(), progress)
+            .map(|(dag, _)| dag)
+    }
+
+    /// Parses a database, calling `progress` after every node line
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    /// * `options` - How to parse the database
+    /// * `progress` - Called with the progress so far after every node line
+    fn read_database(
+        reader: impl Read,
+        options: &ParseOptions,
+        mut progress: impl FnMut(Progress),
+    ) -> Result<(DirectedAcyclicGraph, Vec<ParseWarning>), DagParseError> {
+        let mut lines = DataLines::new(BufReader::new(decompress(reader)?));
+        let mut parser = DatabaseParser::new(options, lines.header()?);
+        while lines.next_line()? {
+            parser.node_line(&lines.line, lines.number)?;
+            progress(parser.progress(lines.bytes));
+        }
+
+        parser.finish()
+    }
+
+    /// Writes the DAG in the database format read by `from_read`
+    /// Nodes with a single parent are written with that parent on both sides
+    /// Fails if node 1 is not the only origin, the node ids are not 1 through N + 1
+    /// or a node has no parents or more than two
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    pub fn to_database(&self, mut writer: impl Write) -> Result<(), DatabaseWriteError> {
+        if self.origins() != [1] {
+            return Err(DatabaseWriteError::UnsupportedOrigins(self.origins.clone()));
+        }
+
+        let size = self.nodes.len().saturating_sub(1);
+        for node in 1..=size + 1 {
+            if !self.nodes.contains(&node) {
+                return Err(DatabaseWriteError::MissingNode(node));
+            }
+        }
+
+        writeln!(writer, "{}", size)?;
+        for node in 2..=size + 1 {
+            match *self.neighbors(node) {
+                [] => return Err(DatabaseWriteError::NoParents(node)),
+                [parent] => writeln!(writer, "{} {}", parent, parent)?,
+                [left, right] => writeln!(writer, "{} {}", left, right)?,
+                ref parents => {
+                    return Err(DatabaseWriteError::TooManyParents {
+                        node,
+                        count: parents.len(),
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds a graph from the lines of a database, independent of how they are read
+pub(crate) struct DatabaseParser<'a> {
+    options: &'a ParseOptions,
+    dag: DirectedAcyclicGraph,
+    /// Parents of the current node line, reused between lines
+    parents: Vec<usize>,
+    /// Id of the last parsed node, 1 before the first node line
+    node: usize,
+    /// Number of node lines announced by the header
+    expected_lines: usize,
+}
+
+impl<'a> DatabaseParser<'a> {
+    /// Creates a parser for the node lines following the header
+    /// The header is used to preallocate the graph, up to a sane limit
+    /// # Arguments
+    /// * `options` - How to parse the database
+    /// * `expected_lines` - Number of node lines announced by the header
+    pub(crate) fn new(options: &'a ParseOptions, expected_lines: usize) -> DatabaseParser<'a> {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.reserve(expected_lines.saturating_add(1).min(MAX_RESERVED_NODES));
+        DatabaseParser {
+            options,
+            dag,
+            parents: Vec::new(),
+            node: 1,
+            expected_lines,
+        }
+    }
+
+    /// Adds the next node and the edges to its parents
+    /// # Arguments
+    /// * `line` - The node line
+    /// * `number` - Line number used for error reporting
+    pub(crate) fn node_line(&mut self, line: &str, number: usize) -> Result<(), DagParseError> {
+        self.node += 1;
+        let node = self.node;
+
+        self.options
+            .parse_parents(line, number, &mut self.parents)?;
+        self.dag.nodes.insert(node);
+        for (i, &parent) in self.parents.iter().enumerate() {
+            if self.options.strict {
+                check_parent(node, parent, self.expected_lines + 1, number)?;
+            }
+            self.dag.nodes.insert(parent);
+            if !self.parents[..i].contains(&parent) {
+                self.dag.add_edge(node, parent);
+                continue;
+            }
+
+            match self.options.duplicate_parents {
+                DuplicateParents::Reject => {
+                    return Err(DagParseError::DuplicateParent {
+                        line: number,
+                        node,
+                        parent,
+                    })
+                }
+                DuplicateParents::Collapse => {}
+                DuplicateParents::Count if parent != node => {
+                    *self.dag.multiplicities.entry((node, parent)).or_insert(1) += 1;
+                }
+                DuplicateParents::Count => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Progress after the last node line
+    /// # Arguments
+    /// * `bytes` - Number of bytes consumed so far
+    pub(crate) fn progress(&self, bytes: u64) -> Progress {
+        Progress {
+            lines: self.node - 1,
+            bytes,
+            expected_lines: self.expected_lines,
+        }
+    }
+
+    /// Checks the parsed lines against the header and returns the graph
+    pub(crate) fn finish(self) -> Result<(DirectedAcyclicGraph, Vec<ParseWarning>), DagParseError> {
+        let DatabaseParser {
+            options,
+            dag,
+            node,
+            expected_lines,
+            ..
+        } = self;
+
+        let mut warnings = Vec::new();
+        let actual = node - 1;
+        if actual != expected_lines {
+            if options.strict {
+                return Err(DagParseError::NodeCountMismatch {
+                    expected: expected_lines,
+                    actual,
+                });
+            }
+            warnings.push(ParseWarning::HeaderMismatch(HeaderMismatch {
+                expected: expected_lines,
+                actual,
+            }));
+        }
+
+        let mut phantoms: Vec<usize> = dag
+            .nodes
+            .iter()
+            .copied()
+            .filter(|&id| id == 0 || id > node)
+            .collect();
+        if !phantoms.is_empty() {
+            phantoms.sort_unstable();
+            warnings.push(ParseWarning::PhantomNodes(phantoms));
+        }
+
+        Ok((dag, warnings))
+    }
+}
+
+/// Reads the lines of a text format, skipping blank lines and `#` comments
+pub(crate) struct DataLines<R> {
+    reader: R,
+    /// The current data line, including its line break
+    pub(crate) line: String,
+    /// 1-based number of the current line
+    pub(crate) number: usize,
+    /// Number of bytes consumed so far, skipped lines included
+    pub(crate) bytes: u64,
+}
+
+impl<R: BufRead> DataLines<R> {
+    /// Creates a reader positioned before the first line
+    /// # Arguments
+    /// * `reader` - The buffered input
+    pub(crate) fn new(reader: R) -> DataLines<R> {
+        DataLines {
+            reader,
+            line: String::new(),
+            number: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Advances to the next data line, returns false at the end of the input
+    pub(crate) fn next_line(&mut self) -> io::Result<bool> {
+        loop {
+            self.line.clear();
+            let read = self.reader.read_line(&mut self.line)?;
+            if read == 0 {
+                return Ok(false);
+            }
+            self.bytes += read as u64;
+            self.number += 1;
+
+            if is_data_line(&self.line) {
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Reads the node count header of the database format
+    pub(crate) fn header(&mut self) -> Result<usize, DagParseError> {
+        let found = self.next_line()?;
+        parse_header(found.then_some(self.line.as_str()), self.number)
+    }
+}
+
+/// Returns if `line` carries data, blank lines and `#` comments do not
+/// # Arguments
+/// * `line` - The raw line
+pub(crate) fn is_data_line(line: &str) -> bool {
+    let data = line.trim_start();
+    !data.is_empty() && !data.starts_with('#')
+}
+
+/// Parses the node count header of the database format
+/// # Arguments
+/// * `line` - The first data line, `None` for an input without one
+/// * `number` - Line number used for error reporting
+pub(crate) fn parse_header(line: Option<&str>, number: usize) -> Result<usize, DagParseError> {
+    let header = line.map_or("", str::trim);
+    header.parse().map_err(|_| DagParseError::BadHeader {
+        line: number.max(1),
+        value: header.to_string(),
+    })
+}
+
+/// Fails if a parent comes after its node or is not a valid node id
+/// # Arguments
+/// * `node` - Id of the node
+/// * `parent` - Id of one of its parents
+/// * `max` - Greatest node id announced by the header
+/// * `line` - Line number used for error reporting
+fn check_parent(node: usize, parent: usize, max: usize, line: usize) -> Result<(), DagParseError> {
+    if parent == 0 || parent > max {
+        Err(DagParseError::ParentOutOfRange { line, parent, max })
+    } else if parent > node {
+        Err(DagParseError::ForwardReference { line, node, parent })
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses a single node id field of a database line
+/// # Arguments
+/// * `field` - The raw field, `None` if the line ended early
+/// * `line` - Line number used for error reporting
+/// * `name` - Human readable field name used for error reporting
+pub(crate) fn parse_field(
+    field: Option<&str>,
+    line: usize,
+    name: &'static str,
+) -> Result<usize, DagParseError> {
+    let field = field.ok_or(DagParseError::MissingField { line, field: name })?;
+    field.parse().map_err(|_| DagParseError::InvalidInteger {
+        line,
+        value: field.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        sample_dag, DagParseError, DatabaseWriteError, DirectedAcyclicGraph, DuplicateParents,
+        HeaderMismatch, ParseOptions, ParseWarning, Progress, SAMPLE_DATABASE,
+    };
+
+    #[test]
+    fn test_streaming_progress() {
+        let database = "3\n1 1\n1 2\n2 3\n";
+
+        let mut reports = Vec::new();
+        let dag = DirectedAcyclicGraph::from_read_streaming(database.as_bytes(), |progress| {
+            reports.push(progress)
+        })
+        .unwrap();
+
+        assert_eq!(dag.nodes().count(), 4);
+        assert_eq!(reports.len(), 3);
+        assert_eq!(
+            reports[2],
+            Progress {
+                lines: 3,
+                bytes: database.len() as u64,
+                expected_lines: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_relaxed_parent_count() {
+        let database = "3\n1\n1 2 1\n1 2 3\n";
+        assert!(matches!(
+            DirectedAcyclicGraph::from_read(database.as_bytes()),
+            Err(DagParseError::MissingField { line: 2, .. })
+        ));
+
+        let options = ParseOptions::new().relaxed(true);
+        let (dag, warnings) =
+            DirectedAcyclicGraph::from_read_with(database.as_bytes(), &options).unwrap();
+        assert_eq!(dag.edge_count(), 6);
+        assert!(warnings.is_empty());
+        assert_eq!(dag.out_degree(4), 3);
+
+        let (dag, _) =
+            DirectedAcyclicGraph::from_read_with("1\n\n1\n".as_bytes(), &options).unwrap();
+        assert_eq!(dag.node_count(), 2);
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let database = "# two nodes\n2\n\n1 1\n  # node 3\n2 1\n\n";
+        let dag = DirectedAcyclicGraph::from_read(database.as_bytes()).unwrap();
+        assert_eq!(dag.node_count(), 3);
+        assert!(dag.contains_edge(3, 2));
+
+        let broken = DirectedAcyclicGraph::from_read("2\n# comment\n1\n".as_bytes());
+        assert!(matches!(
+            broken,
+            Err(DagParseError::MissingField { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_header_mismatch() {
+        let database = "4\n1 1\n1 2\n";
+        let (dag, warnings) =
+            DirectedAcyclicGraph::from_read_with(database.as_bytes(), &ParseOptions::new())
+                .unwrap();
+        assert_eq!(dag.node_count(), 3);
+        assert_eq!(
+            warnings,
+            [ParseWarning::HeaderMismatch(HeaderMismatch {
+                expected: 4,
+                actual: 2
+            })]
+        );
+
+        let strict = ParseOptions::new().strict(true);
+        assert!(matches!(
+            DirectedAcyclicGraph::from_read_with(database.as_bytes(), &strict),
+            Err(DagParseError::NodeCountMismatch {
+                expected: 4,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_forward_references() {
+        let database = "3\n1 1\n1 4\n2 7\n";
+        let (_, warnings) =
+            DirectedAcyclicGraph::from_read_with(database.as_bytes(), &ParseOptions::new())
+                .unwrap();
+        assert_eq!(warnings, [ParseWarning::PhantomNodes(vec![7])]);
+
+        let strict = ParseOptions::new().strict(true);
+        assert!(matches!(
+            DirectedAcyclicGraph::from_read_with(database.as_bytes(), &strict),
+            Err(DagParseError::ForwardReference {
+                line: 3,
+                node: 3,
+                parent: 4
+            })
+        ));
+        assert!(matches!(
+            DirectedAcyclicGraph::from_read_with("3\n1 1\n1 2\n2 7\n".as_bytes(), &strict),
+            Err(DagParseError::ParentOutOfRange {
+                line: 4,
+                parent: 7,
+                max: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_parents() {
+        let database = "2\n1 1\n1 2\n";
+        let dag = DirectedAcyclicGraph::from_read(database.as_bytes()).unwrap();
+        assert_eq!(dag.edge_multiplicity(2, 1), Some(1));
+
+        let count = ParseOptions::new().duplicate_parents(DuplicateParents::Count);
+        let (dag, _) = DirectedAcyclicGraph::from_read_with(database.as_bytes(), &count).unwrap();
+        assert_eq!(dag.edge_count(), 3);
+        assert_eq!(dag.edge_multiplicity(2, 1), Some(2));
+        assert_eq!(dag.edge_multiplicity(3, 1), Some(1));
+        assert_eq!(dag.edge_multiplicity(1, 3), None);
+
+        let reject = ParseOptions::new().duplicate_parents(DuplicateParents::Reject);
+        assert!(matches!(
+            DirectedAcyclicGraph::from_read_with(database.as_bytes(), &reject),
+            Err(DagParseError::DuplicateParent {
+                line: 2,
+                node: 2,
+                parent: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_path() {
+        let database = "3\n1 1\n1 2\n2 3\n";
+        let path = std::env::temp_dir().join(format!("dag-{}.txt", std::process::id()));
+        std::fs::write(&path, database).unwrap();
+
+        let dag = DirectedAcyclicGraph::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            dag.unwrap(),
+            DirectedAcyclicGraph::from_read(database.as_bytes()).unwrap()
+        );
+        assert!(matches!(
+            DirectedAcyclicGraph::from_path(&path),
+            Err(DagParseError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_database_round_trip() {
+        let mut dag = sample_dag();
+        let mut written = Vec::new();
+        dag.to_database(&mut written).unwrap();
+        assert_eq!(String::from_utf8(written).unwrap(), SAMPLE_DATABASE);
+
+        dag.add_edge(5, 4);
+        assert!(matches!(
+            dag.to_database(Vec::new()),
+            Err(DatabaseWriteError::TooManyParents { node: 5, count: 3 })
+        ));
+
+        dag.add_edge(8, 1);
+        assert!(matches!(
+            dag.to_database(Vec::new()),
+            Err(DatabaseWriteError::MissingNode(7))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::{vec, vec::Vec};
+
+use crate::hash::hash_map::Entry;
+use crate::hash::{HashMap, HashSet};
+use crate::{average, DagView, DirectedAcyclicGraph, NodeMap};
+
+impl DirectedAcyclicGraph {
+    /// Orders the nodes so that every node comes before the nodes it references
+    /// The origin is therefore always at the end of the order
+    /// Returns `None` if the graph contains a cycle
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut in_degrees: NodeMap<usize, usize> = self
+            .nodes()
+            .map(|node| (node, self.predecessors(node).len()))
+            .collect();
+
+        let mut queue: VecDeque<usize> = in_degrees
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(node, _)| *node)
+            .collect();
+
+        let mut order = Vec::with_capacity(in_degrees.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for neighbor in self.neighbors(node) {
+                let degree = in_degrees.get_mut(neighbor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+
+        if order.len() == in_degrees.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    /// Length (edge count) of the shortest path from `node` to an origin
+    /// Only the part of the graph reachable from `node` is visited
+    /// Returns `None` if the node does not exist or has no path to an origin
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn min_depth_of(&self, node: usize) -> Option<usize> {
+        if !self.nodes.contains(&node) {
+            return None;
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(node);
+        queue.push_back((node, 0));
+        while let Some((current, depth)) = queue.pop_front() {
+            if self.is_origin(current) {
+                return Some(depth);
+            }
+            for &neighbor in self.neighbors(current) {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Length (edge count) of the longest path from `node` to an origin
+    /// Only the part of the graph reachable from `node` is visited
+    /// Returns `None` if the node does not exist, has no path to an origin
+    /// or reaches a cycle, which makes the longest path unbounded
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn depth_of(&self, node: usize) -> Option<usize> {
+        if !self.nodes.contains(&node) {
+            return None;
+        }
+
+        // Post-order DFS, a node's depth is known once all of its neighbors are finished
+        let mut depths: HashMap<usize, Option<usize>> = HashMap::new();
+        let mut on_path = HashSet::new();
+        let mut stack = vec![(node, 0)];
+        on_path.insert(node);
+        while let Some((current, index)) = stack.last_mut() {
+            let current = *current;
+            let neighbor = if self.is_origin(current) {
+                None
+            } else {
+                self.neighbors(current).get(*index).copied()
+            };
+
+            match neighbor {
+                Some(neighbor) => {
+                    *index += 1;
+                    if on_path.contains(&neighbor) {
+                        return None;
+                    }
+                    if !depths.contains_key(&neighbor) {
+                        on_path.insert(neighbor);
+                        stack.push((neighbor, 0));
+                    }
+                }
+                None => {
+                    let depth = if self.is_origin(current) {
+                        Some(0)
+                    } else {
+                        self.neighbors(current)
+                            .iter()
+                            .filter_map(|neighbor| depths[neighbor])
+                            .max()
+                            .map(|depth| depth + 1)
+                    };
+                    depths.insert(current, depth);
+                    on_path.remove(&current);
+                    stack.pop();
+                }
+            }
+        }
+
+        depths[&node]
+    }
+
+    /// Longest depth of every node that can reach an origin, see `depth_of`
+    /// Computed in a single pass over the topological order
+    /// Returns `None` if the graph is cyclic
+    pub fn longest_depths(&self) -> Option<HashMap<usize, usize>> {
+        let order = self.topological_order()?;
+        Some(self.max_depths(&order))
+    }
+
+    /// Number of nodes at each shortest depth, indexed by depth so the origins are at 0
+    /// Levels are found by a breadth first layering from the origins,
+    /// nodes without a path to an origin are not counted
+    pub fn level_sizes(&self) -> Vec<usize> {
+        self.depth_distribution().counts().to_vec()
+    }
+
+    /// Largest number of nodes sharing the same shortest depth
+    /// Returns 0 for an empty DAG
+    pub fn width(&self) -> usize {
+        self.level_sizes().into_iter().max().unwrap_or(0)
+    }
+
+    /// Shortest depth (edge count) of every node that can reach an origin, see `min_depths`
+    pub(crate) fn min_depths(&self) -> HashMap<usize, usize> {
+        min_depths(self)
+    }
+
+    /// Longest depth (edge count) of every node that can reach an origin, see `max_depths`
+    /// # Arguments
+    /// * `order` - Topological order of the DAG, as returned by `topological_order`
+    pub(crate) fn max_depths(&self, order: &[usize]) -> HashMap<usize, usize> {
+        max_depths(self, order)
+    }
+
+    /// Number of paths to an origin by path length, see `path_length_counts`
+    /// # Arguments
+    /// * `order` - Topological order of the DAG, as returned by `topological_order`
+    pub(crate) fn path_length_counts(&self, order: &[usize]) -> BTreeMap<usize, u128> {
+        path_length_counts(self, order)
+    }
+}
+
+/// Shortest depth (edge count) of every node that can reach an origin
+/// Computed in a single breadth first pass from the origins over reverse edges
+/// # Arguments
+/// * `graph` - Graph to measure
+pub(crate) fn min_depths<G: DagView + ?Sized>(graph: &G) -> HashMap<usize, usize> {
+    let mut depths = HashMap::new();
+    let mut queue = VecDeque::new();
+    for origin in graph.origin_ids() {
+        depths.insert(origin, 0);
+        queue.push_back(origin);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let depth = depths[&node];
+        for predecessor in graph.child_ids(node) {
+            if let Entry::Vacant(entry) = depths.entry(predecessor) {
+                entry.insert(depth + 1);
+                queue.push_back(predecessor);
+            }
+        }
+    }
+
+    depths
+}
+
+/// Longest depth (edge count) of every node that can reach an origin
+/// # Arguments
+/// * `graph` - Graph to measure
+/// * `order` - Topological order of the graph
+pub(crate) fn max_depths<G: DagView + ?Sized>(graph: &G, order: &[usize]) -> HashMap<usize, usize> {
+    let mut depths = HashMap::new();
+    for &node in order.iter().rev() {
+        if graph.is_origin(node) {
+            depths.insert(node, 0);
+            continue;
+        }
+
+        let deepest = graph
+            .parent_ids(node)
+            .filter_map(|neighbor| depths.get(&neighbor))
+            .max()
+            .copied();
+        if let Some(deepest) = deepest {
+            depths.insert(node, deepest + 1);
+        }
+    }
+
+    depths
+}
+
+/// Number of paths to an origin by path length (counted in nodes), summed over all non-origin nodes
+/// Counts saturate at `u128::MAX` instead of overflowing
+/// # Arguments
+/// * `graph` - Graph to measure
+/// * `order` - Topological order of the graph
+pub(crate) fn path_length_counts<G: DagView + ?Sized>(
+    graph: &G,
+    order: &[usize],
+) -> BTreeMap<usize, u128> {
+    // Index i of a node's table holds the number of its paths made of i nodes
+    let mut tables: HashMap<usize, Vec<u128>> = HashMap::new();
+    let mut counts = BTreeMap::new();
+    for &node in order.iter().rev() {
+        if graph.is_origin(node) {
+            tables.insert(node, vec![0, 1]);
+            continue;
+        }
+
+        let mut table: Vec<u128> = Vec::new();
+        for neighbor in graph.parent_ids(node) {
+            if let Some(neighbor_table) = tables.get(&neighbor) {
+                if table.len() < neighbor_table.len() + 1 {
+                    table.resize(neighbor_table.len() + 1, 0);
+                }
+                for (length, count) in neighbor_table.iter().enumerate() {
+                    table[length + 1] = table[length + 1].saturating_add(*count);
+                }
+            }
+        }
+
+        for (length, count) in table.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let total = counts.entry(length).or_insert(0u128);
+            *total = total.saturating_add(*count);
+        }
+        tables.insert(node, table);
+    }
+
+    counts
+}
+
+/// Average of path counts over the path lengths that occur
+/// # Arguments
+/// * `counts` - Output of `path_length_counts`
+pub(crate) fn avg_paths_per_length(counts: &BTreeMap<usize, u128>) -> Option<f64> {
+    average(
+        counts.values().map(|count| *count as f64).sum::<f64>(),
+        counts.len(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_depth_tables() {
+        let dag = sample_dag();
+        let order = dag.topological_order().unwrap();
+
+        for (from, to) in dag.edges() {
+            let from = order.iter().position(|node| *node == from).unwrap();
+            let to = order.iter().position(|node| *node == to).unwrap();
+            assert!(from < to);
+        }
+
+        let min = dag.min_depths();
+        let max = dag.max_depths(&order);
+        assert_eq!(min[&5], 2);
+        assert_eq!(max[&5], 4);
+        assert_eq!(min[&1], 0);
+        assert_eq!(max[&1], 0);
+
+        for node in dag.nodes() {
+            assert_eq!(dag.min_depth_of(node), min.get(&node).copied());
+            assert_eq!(dag.depth_of(node), max.get(&node).copied());
+        }
+        assert_eq!(dag.depth_of(7), None);
+        assert_eq!(dag.longest_depths(), Some(max));
+
+        let counts = dag.path_length_counts(&order);
+        assert_eq!(counts.values().sum::<u128>(), 10);
+        assert_eq!(counts[&5], 1);
+    }
+
+    #[test]
+    fn test_level_sizes() {
+        let dag = sample_dag();
+        assert_eq!(dag.level_sizes(), [1, 2, 3]);
+        assert_eq!(dag.width(), dag.statistics().width);
+        assert_eq!(DirectedAcyclicGraph::new().width(), 0);
+    }
+}
@@ -0,0 +1,113 @@
+use alloc::{string::ToString, vec::Vec};
+use core::fmt;
+
+use crate::{DirectedAcyclicGraph, Statistics};
+
+/// Structural and statistical differences between two snapshots of a DAG,
+/// computed by `DirectedAcyclicGraph::diff`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DagDiff {
+    /// Nodes only present in the newer snapshot, sorted ascending
+    pub added_nodes: Vec<usize>,
+    /// Nodes only present in the older snapshot, sorted ascending
+    pub removed_nodes: Vec<usize>,
+    /// Edges only present in the newer snapshot, sorted ascending
+    pub added_edges: Vec<(usize, usize)>,
+    /// Edges only present in the older snapshot, sorted ascending
+    pub removed_edges: Vec<(usize, usize)>,
+    /// Statistics of the older snapshot
+    pub before: Statistics,
+    /// Statistics of the newer snapshot
+    pub after: Statistics,
+}
+
+impl DagDiff {
+    /// Returns if both snapshots have the same nodes and edges
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Compares this DAG with a newer snapshot `other`
+    /// # Arguments
+    /// * `other` - The newer snapshot
+    pub fn diff(&self, other: &DirectedAcyclicGraph) -> DagDiff {
+        let only_in = |a: &DirectedAcyclicGraph, b: &DirectedAcyclicGraph| {
+            let mut nodes: Vec<usize> = a.nodes().filter(|node| !b.nodes.contains(node)).collect();
+            nodes.sort_unstable();
+            let mut edges: Vec<(usize, usize)> = a
+                .edges()
+                .filter(|(from, to)| !b.neighbors(*from).contains(to))
+                .collect();
+            edges.sort_unstable();
+            (nodes, edges)
+        };
+        let (added_nodes, added_edges) = only_in(other, self);
+        let (removed_nodes, removed_edges) = only_in(self, other);
+
+        DagDiff {
+            added_nodes,
+            removed_nodes,
+            added_edges,
+            removed_edges,
+            before: self.statistics(),
+            after: other.statistics(),
+        }
+    }
+}
+
+impl fmt::Display for DagDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ADDED NODES: {}", self.added_nodes.len())?;
+        writeln!(f, "REMOVED NODES: {}", self.removed_nodes.len())?;
+        writeln!(f, "ADDED EDGES: {}", self.added_edges.len())?;
+        write!(f, "REMOVED EDGES: {}", self.removed_edges.len())?;
+
+        // Statistics are printed side by side as `LABEL: before -> after`
+        let before = self.before.to_string();
+        let after = self.after.to_string();
+        for (before, after) in before.lines().zip(after.lines()) {
+            match (before.split_once(": "), after.split_once(": ")) {
+                (Some((label, before)), Some((_, after))) => {
+                    write!(f, "\n{}: {} -> {}", label, before, after)?
+                }
+                _ => write!(f, "\n{} -> {}", before, after)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_diff() {
+        let mut before = DirectedAcyclicGraph::new();
+        before.add_edge(2, 1);
+        before.add_edge(3, 2);
+        before.add_edge(4, 3);
+
+        let mut after = DirectedAcyclicGraph::new();
+        after.add_edge(2, 1);
+        after.add_edge(3, 2);
+        after.add_edge(5, 3);
+        after.add_edge(5, 2);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_nodes, [5]);
+        assert_eq!(diff.removed_nodes, [4]);
+        assert_eq!(diff.added_edges, [(5, 2), (5, 3)]);
+        assert_eq!(diff.removed_edges, [(4, 3)]);
+        assert_eq!(diff.after.edge_count, 4);
+        assert!(diff.to_string().contains("EDGES: 3 -> 4"));
+
+        assert!(before.diff(&before).is_empty());
+    }
+}
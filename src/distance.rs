@@ -0,0 +1,175 @@
+use alloc::{vec, vec::Vec};
+
+use crate::hash::HashMap;
+use crate::DirectedAcyclicGraph;
+
+/// Distances from one source found by `DirectedAcyclicGraph::distance_summaries`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct DistanceSummary {
+    /// Largest distance to a reached node
+    eccentricity: usize,
+    /// Number of reached nodes, the source included
+    reached: usize,
+    /// Sum of the distances to every reached node
+    total: usize,
+}
+
+impl DirectedAcyclicGraph {
+    /// Largest number of edges between `node` and any node connected to it, ignoring edge direction
+    /// Returns `None` if the node does not exist
+    /// # Arguments
+    /// * `node` - Node id to measure from
+    pub fn eccentricity(&self, node: usize) -> Option<usize> {
+        if !self.nodes.contains(&node) {
+            return None;
+        }
+
+        Some(self.distance_summaries(&[node])[0].eccentricity)
+    }
+
+    /// `eccentricity` of every node
+    pub fn eccentricities(&self) -> HashMap<usize, usize> {
+        let nodes: Vec<usize> = self.nodes().collect();
+        let summaries = self.distance_summaries(&nodes);
+        nodes
+            .into_iter()
+            .zip(summaries.into_iter().map(|summary| summary.eccentricity))
+            .collect()
+    }
+
+    /// Smallest eccentricity of any node
+    /// Returns `None` for an empty DAG
+    pub fn radius(&self) -> Option<usize> {
+        self.eccentricities().into_values().min()
+    }
+
+    /// Largest eccentricity of any node, the diameter of the graph ignoring edge direction,
+    /// see `diameter` for the paths along the edges
+    /// Returns `None` for an empty DAG
+    pub fn undirected_diameter(&self) -> Option<usize> {
+        self.eccentricities().into_values().max()
+    }
+
+    /// Closeness centrality of `node` ignoring edge direction, the inverse average distance
+    /// to the nodes connected to it, scaled by the share of the graph it is connected to
+    /// (Wasserman and Faust) so nodes of small components do not score highest
+    /// Returns `None` if the node does not exist
+    /// # Arguments
+    /// * `node` - Node id to measure from
+    pub fn closeness_centrality(&self, node: usize) -> Option<f64> {
+        if !self.nodes.contains(&node) {
+            return None;
+        }
+
+        let summary = self.distance_summaries(&[node])[0];
+        if summary.total == 0 {
+            return Some(0.0);
+        }
+        let others = (summary.reached - 1) as f64;
+        Some(others / summary.total as f64 * others / (self.nodes.len() - 1) as f64)
+    }
+
+    /// Breadth first searches from every source ignoring edge direction, run 64 sources
+    /// at a time as a multi-source BFS with one bit per source in each node's frontier
+    /// # Arguments
+    /// * `sources` - Existing node ids to measure from
+    fn distance_summaries(&self, sources: &[usize]) -> Vec<DistanceSummary> {
+        let nodes: Vec<usize> = self.nodes().collect();
+        let index: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let adjacency: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|&node| {
+                self.neighbors(node)
+                    .iter()
+                    .chain(self.predecessors(node))
+                    .map(|n| index[n])
+                    .collect()
+            })
+            .collect();
+
+        let mut summaries = Vec::with_capacity(sources.len());
+        for batch in sources.chunks(64) {
+            let mut batch_summaries = vec![
+                DistanceSummary {
+                    reached: 1,
+                    ..DistanceSummary::default()
+                };
+                batch.len()
+            ];
+            let mut seen = vec![0u64; nodes.len()];
+            let mut frontier = vec![0u64; nodes.len()];
+            for (bit, source) in batch.iter().enumerate() {
+                seen[index[source]] |= 1 << bit;
+                frontier[index[source]] |= 1 << bit;
+            }
+
+            let mut distance = 0;
+            loop {
+                distance += 1;
+                let mut next = vec![0u64; nodes.len()];
+                for (node, &bits) in frontier.iter().enumerate() {
+                    if bits != 0 {
+                        for &neighbor in &adjacency[node] {
+                            next[neighbor] |= bits;
+                        }
+                    }
+                }
+
+                let mut advanced = false;
+                for (node, bits) in next.iter_mut().enumerate() {
+                    *bits &= !seen[node];
+                    seen[node] |= *bits;
+
+                    let mut remaining = *bits;
+                    while remaining != 0 {
+                        let summary = &mut batch_summaries[remaining.trailing_zeros() as usize];
+                        summary.eccentricity = distance;
+                        summary.reached += 1;
+                        summary.total += distance;
+                        remaining &= remaining - 1;
+                        advanced = true;
+                    }
+                }
+
+                if !advanced {
+                    break;
+                }
+                frontier = next;
+            }
+
+            summaries.extend(batch_summaries);
+        }
+
+        summaries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_distance_measures() {
+        let mut dag: DirectedAcyclicGraph =
+            vec![(2, 1), (3, 1), (4, 2), (5, 4)].into_iter().collect();
+        dag.add_node(9);
+
+        assert_eq!(dag.eccentricity(1), Some(3));
+        assert_eq!(dag.eccentricity(5), Some(4));
+        assert_eq!(dag.eccentricity(2), Some(2));
+        assert_eq!(dag.eccentricity(9), Some(0));
+        assert_eq!(dag.eccentricity(7), None);
+        assert_eq!(dag.radius(), Some(0));
+        assert_eq!(dag.undirected_diameter(), Some(4));
+
+        // 2 reaches 1, 4 at distance 1 and 3, 5 at distance 2, 4 of the 5 other nodes
+        assert_eq!(dag.closeness_centrality(2), Some(4.0 / 6.0 * 4.0 / 5.0));
+        assert_eq!(dag.closeness_centrality(9), Some(0.0));
+
+        let chain: DirectedAcyclicGraph = (2..=100).map(|node| (node, node - 1)).collect();
+        let eccentricities = chain.eccentricities();
+        assert_eq!(eccentricities[&1], 99);
+        assert_eq!(eccentricities[&50], 50);
+        assert_eq!(chain.radius(), Some(50));
+    }
+}
@@ -0,0 +1,146 @@
+use alloc::{vec, vec::Vec};
+
+use crate::hash::HashMap;
+use crate::DirectedAcyclicGraph;
+
+/// Dominator tree of a DAG rooted at its origins, see `DirectedAcyclicGraph::dominators`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dominators {
+    /// Immediate dominator of every node that has one
+    immediate: HashMap<usize, usize>,
+}
+
+impl Dominators {
+    /// Closest node other than `node` itself that every path from `node` to an origin passes through
+    /// Returns `None` for origins, nodes without a path to an origin and nodes whose paths
+    /// only meet at different origins
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn immediate_dominator(&self, node: usize) -> Option<usize> {
+        self.immediate.get(&node).copied()
+    }
+
+    /// Every dominator of `node` other than itself, from the closest one up to the root
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn dominators(&self, node: usize) -> Vec<usize> {
+        let mut dominators = Vec::new();
+        let mut current = node;
+        while let Some(dominator) = self.immediate_dominator(current) {
+            dominators.push(dominator);
+            current = dominator;
+        }
+
+        dominators
+    }
+
+    /// Returns if every path from `node` to an origin passes through `dominator`
+    /// A node dominates itself
+    /// # Arguments
+    /// * `dominator` - Candidate dominator
+    /// * `node` - Node id to look up
+    pub fn dominates(&self, dominator: usize, node: usize) -> bool {
+        dominator == node || self.dominators(node).contains(&dominator)
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Dominator tree with the origins as root, where a node dominates another if every path
+    /// from the other node to an origin passes through it, e.g. a bottleneck of a commit graph
+    /// Computed in a single pass over the topological order, several origins share a virtual root
+    /// Returns `None` if the graph is cyclic
+    pub fn dominators(&self) -> Option<Dominators> {
+        let order = self.topological_order()?;
+
+        // Index 0 is the virtual root above the origins, nodes are indexed parents first
+        let mut index: HashMap<usize, usize> = HashMap::new();
+        let mut nodes = vec![0];
+        let mut immediate = vec![0];
+        for &node in order.iter().rev() {
+            let dominator = if self.is_origin(node) {
+                Some(0)
+            } else {
+                self.neighbors(node)
+                    .iter()
+                    .filter_map(|neighbor| index.get(neighbor).copied())
+                    .reduce(|a, b| intersect(&immediate, a, b))
+            };
+
+            if let Some(dominator) = dominator {
+                index.insert(node, nodes.len());
+                nodes.push(node);
+                immediate.push(dominator);
+            }
+        }
+
+        let immediate = immediate
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, &dominator)| dominator != 0)
+            .map(|(i, &dominator)| (nodes[i], nodes[dominator]))
+            .collect();
+        Some(Dominators { immediate })
+    }
+}
+
+/// Closest common dominator of two nodes, walking up from whichever comes later in the order
+/// # Arguments
+/// * `immediate` - Immediate dominator index of every node index found so far
+/// * `a` - Index of the first node
+/// * `b` - Index of the second node
+fn intersect(immediate: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while a > b {
+            a = immediate[a];
+        }
+        while b > a {
+            b = immediate[b];
+        }
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_dominators() {
+        let mut dag: DirectedAcyclicGraph = vec![
+            (2, 1),
+            (3, 1),
+            (4, 2),
+            (4, 3),
+            (5, 4),
+            (6, 5),
+            (7, 6),
+            (7, 4),
+        ]
+        .into_iter()
+        .collect();
+        dag.add_node(9);
+        let dominators = dag.dominators().unwrap();
+
+        assert_eq!(dominators.immediate_dominator(1), None);
+        assert_eq!(dominators.immediate_dominator(2), Some(1));
+        assert_eq!(dominators.immediate_dominator(4), Some(1));
+        assert_eq!(dominators.immediate_dominator(6), Some(5));
+        assert_eq!(dominators.immediate_dominator(7), Some(4));
+        assert_eq!(dominators.immediate_dominator(9), None);
+        assert_eq!(dominators.dominators(6), [5, 4, 1]);
+        assert!(dominators.dominates(4, 7) && !dominators.dominates(5, 7));
+
+        let mut dag = DirectedAcyclicGraph::with_origins(vec![1, 10]);
+        dag.add_edge(2, 1);
+        dag.add_edge(2, 10);
+        dag.add_edge(3, 2);
+        let dominators = dag.dominators().unwrap();
+        assert_eq!(dominators.immediate_dominator(2), None);
+        assert_eq!(dominators.immediate_dominator(3), Some(2));
+
+        dag.add_edge(1, 3);
+        assert!(dag.dominators().is_none());
+    }
+}
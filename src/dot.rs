@@ -0,0 +1,569 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use crate::import::{assign_ids, resolve_origins};
+use crate::{DagParseError, DirectedAcyclicGraph};
+
+/// Lexical token of the DOT language
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Identifier, number or quoted string
+    Id(String),
+    /// `->`
+    Arrow,
+    /// `--`, only valid in undirected graphs
+    UndirectedEdge,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Separator,
+}
+
+/// Node or edge statement of a DOT graph, other statements are dropped while parsing
+enum Statement {
+    Node {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    Edge {
+        chain: Vec<String>,
+        attributes: Vec<(String, String)>,
+        line: usize,
+    },
+}
+
+impl DirectedAcyclicGraph {
+    /// Creates a new Directed Acyclic Graph from a Graphviz DOT digraph
+    /// Node names that are integers are used as node ids, other names get fresh ids
+    /// and keep their original name in the `name` attribute
+    /// Node attributes are stored as node attributes and the `weight` edge attribute as edge weight
+    /// Node 1 is the origin if present, otherwise every node without out-references is an origin
+    /// Subgraphs are flattened and graph level attributes are ignored
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    pub fn from_dot(mut reader: impl Read) -> Result<DirectedAcyclicGraph, DagParseError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+
+        let statements = parse_statements(&tokenize(&source)?)?;
+
+        let names = statements.iter().flat_map(|statement| match statement {
+            Statement::Node { name, .. } => std::slice::from_ref(name).iter(),
+            Statement::Edge { chain, .. } => chain.iter(),
+        });
+        let ids = assign_ids(names.map(String::as_str))?;
+
+        let mut dag = DirectedAcyclicGraph::new();
+        for (name, id) in &ids {
+            dag.nodes.insert(*id);
+            if name.parse::<usize>().is_err() {
+                dag.set_node_attr(*id, "name", name.as_str());
+            }
+        }
+
+        for statement in statements {
+            match statement {
+                Statement::Node { name, attributes } => {
+                    for (key, value) in attributes {
+                        dag.set_node_attr(ids[&name], key, value);
+                    }
+                }
+                Statement::Edge {
+                    chain,
+                    attributes,
+                    line,
+                } => {
+                    let weight = attributes
+                        .iter()
+                        .find(|(key, _)| key == "weight")
+                        .map(|(_, value)| {
+                            value.parse::<f64>().map_err(|_| DagParseError::Syntax {
+                                line,
+                                message: format!("invalid edge weight {:?}", value),
+                            })
+                        })
+                        .transpose()?;
+
+                    for pair in chain.windows(2) {
+                        let (from, to) = (ids[&pair[0]], ids[&pair[1]]);
+                        match weight {
+                            Some(weight) => dag.add_weighted_edge(from, to, weight),
+                            None => dag.add_edge(from, to),
+                        };
+                    }
+                }
+            }
+        }
+
+        resolve_origins(&mut dag);
+        Ok(dag)
+    }
+
+    /// Writes the DAG in Graphviz DOT format
+    /// Node attributes become DOT node attributes and explicit edge weights become `weight` attributes
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    pub fn to_dot(&self, writer: impl Write) -> std::io::Result<()> {
+        self.write_dot(writer, false)
+    }
+
+    /// Writes the DAG in Graphviz DOT format with the nodes of each depth level on the same rank
+    /// Nodes that can not reach the origin are left unranked
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    pub fn to_dot_ranked(&self, writer: impl Write) -> std::io::Result<()> {
+        self.write_dot(writer, true)
+    }
+
+    /// Writes the DAG in Graphviz DOT format, shared by `to_dot` and `to_dot_ranked`
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    /// * `ranked` - Whether the nodes of each depth level are put on the same rank
+    fn write_dot(&self, mut writer: impl Write, ranked: bool) -> std::io::Result<()> {
+        writeln!(writer, "digraph dag {{")?;
+
+        let mut nodes: Vec<usize> = self.nodes().collect();
+        nodes.sort_unstable();
+        for node in nodes {
+            write!(writer, "    {}", node)?;
+            if let Some(attributes) = self.node_attrs(node) {
+                let attributes: BTreeMap<_, _> = attributes.iter().collect();
+                let attributes: Vec<String> = attributes
+                    .into_iter()
+                    .map(|(key, value)| format!("{}={}", quote(key), quote(value)))
+                    .collect();
+                write!(writer, " [{}]", attributes.join(", "))?;
+            }
+            writeln!(writer, ";")?;
+        }
+
+        let mut edges: Vec<(usize, usize)> = self.edges().collect();
+        edges.sort_unstable();
+        for (from, to) in edges {
+            write!(writer, "    {} -> {}", from, to)?;
+            if let Some(weight) = self.weights.get(&(from, to)) {
+                write!(writer, " [weight={}]", quote(&weight.to_string()))?;
+            }
+            writeln!(writer, ";")?;
+        }
+
+        if ranked {
+            let mut levels: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+            for (node, depth) in self.min_depths() {
+                levels.entry(depth).or_default().push(node);
+            }
+            for mut level in levels.into_values() {
+                level.sort_unstable();
+                let level: Vec<String> = level.iter().map(|node| node.to_string()).collect();
+                writeln!(writer, "    {{ rank=same; {}; }}", level.join("; "))?;
+            }
+        }
+
+        writeln!(writer, "}}")
+    }
+}
+
+/// Splits DOT source into tokens tagged with their line number, dropping comments
+/// # Arguments
+/// * `source` - DOT source text
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, DagParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut line = 1;
+    let mut line_start = true;
+
+    while let Some(c) = chars.next() {
+        let start_of_line = line_start;
+        line_start = c == '\n' || (line_start && c.is_whitespace());
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' if start_of_line => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            previous = c;
+                        }
+                        None => {
+                            return Err(DagParseError::Syntax {
+                                line,
+                                message: "unterminated comment".to_string(),
+                            })
+                        }
+                    }
+                }
+            }
+            '{' => tokens.push((Token::LBrace, line)),
+            '}' => tokens.push((Token::RBrace, line)),
+            '[' => tokens.push((Token::LBracket, line)),
+            ']' => tokens.push((Token::RBracket, line)),
+            '=' => tokens.push((Token::Equals, line)),
+            ';' | ',' => tokens.push((Token::Separator, line)),
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push((Token::Arrow, line));
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                tokens.push((Token::UndirectedEdge, line));
+            }
+            '"' => {
+                let start = line;
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            value.push('"');
+                        }
+                        Some('\\') if chars.peek() == Some(&'\\') => {
+                            chars.next();
+                            value.push('\\');
+                        }
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            value.push(c);
+                        }
+                        None => {
+                            return Err(DagParseError::Syntax {
+                                line: start,
+                                message: "unterminated string".to_string(),
+                            })
+                        }
+                    }
+                }
+                tokens.push((Token::Id(value), start));
+            }
+            c if is_id_char(c)
+                || (c == '-'
+                    && chars
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_digit() || *c == '.')) =>
+            {
+                let mut value = c.to_string();
+                while let Some(c) = chars.peek().copied().filter(|c| is_id_char(*c)) {
+                    value.push(c);
+                    chars.next();
+                }
+                tokens.push((Token::Id(value), line));
+            }
+            c => {
+                return Err(DagParseError::Syntax {
+                    line,
+                    message: format!("unexpected character {:?}", c),
+                })
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Returns if `c` can be part of an unquoted DOT identifier or number
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Parses the node and edge statements of a tokenized digraph
+/// # Arguments
+/// * `tokens` - Output of `tokenize`
+fn parse_statements(tokens: &[(Token, usize)]) -> Result<Vec<Statement>, DagParseError> {
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+
+    if parser.peek_keyword("strict") {
+        parser.position += 1;
+    }
+    if !parser.peek_keyword("digraph") {
+        return Err(parser.error("expected `digraph`"));
+    }
+    parser.position += 1;
+    if let Some(Token::Id(_)) = parser.peek() {
+        parser.position += 1;
+    }
+    parser.expect(Token::LBrace)?;
+
+    let mut statements = Vec::new();
+    let mut depth = 0;
+    loop {
+        match parser.next() {
+            None => return Err(parser.error("unexpected end of input, expected `}`")),
+            Some(Token::Separator) => {}
+            Some(Token::LBrace) => depth += 1,
+            Some(Token::RBrace) if depth == 0 => break,
+            Some(Token::RBrace) => depth -= 1,
+            Some(Token::Id(id)) if id.eq_ignore_ascii_case("subgraph") => {
+                if let Some(Token::Id(_)) = parser.peek() {
+                    parser.position += 1;
+                }
+                parser.expect(Token::LBrace)?;
+                depth += 1;
+            }
+            Some(Token::Id(id))
+                if ["graph", "node", "edge"]
+                    .iter()
+                    .any(|keyword| id.eq_ignore_ascii_case(keyword)) =>
+            {
+                parser.attributes()?;
+            }
+            Some(Token::Id(id)) => {
+                let line = parser.line();
+                if parser.peek() == Some(&Token::Equals) {
+                    parser.position += 1;
+                    parser.id()?;
+                    continue;
+                }
+
+                let mut chain = vec![id];
+                loop {
+                    match parser.peek() {
+                        Some(Token::Arrow) => {
+                            parser.position += 1;
+                            chain.push(parser.id()?);
+                        }
+                        Some(Token::UndirectedEdge) => {
+                            return Err(parser.error("undirected edges are not supported"))
+                        }
+                        _ => break,
+                    }
+                }
+
+                let attributes = parser.attributes()?;
+                if chain.len() == 1 {
+                    statements.push(Statement::Node {
+                        name: chain.pop().unwrap(),
+                        attributes,
+                    });
+                } else {
+                    statements.push(Statement::Edge {
+                        chain,
+                        attributes,
+                        line,
+                    });
+                }
+            }
+            Some(token) => {
+                let message = format!("unexpected token {:?}", token);
+                return Err(parser.error(&message));
+            }
+        }
+    }
+
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected input after the closing `}`"));
+    }
+
+    Ok(statements)
+}
+
+/// Cursor over the tokens of a DOT graph
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+}
+
+impl Parser<'_> {
+    /// Returns the current token without consuming it, None at the end of the input
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    /// Returns if the current token is `keyword`, compared case insensitively as DOT does
+    /// # Arguments
+    /// * `keyword` - Keyword to look for
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Consumes and returns the current token, None at the end of the input
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    /// Line of the most recently consumed token
+    fn line(&self) -> usize {
+        let index = self
+            .position
+            .saturating_sub(1)
+            .min(self.tokens.len().saturating_sub(1));
+        self.tokens.get(index).map_or(1, |(_, line)| *line)
+    }
+
+    /// Builds a syntax error at the line of the most recently consumed token
+    /// # Arguments
+    /// * `message` - Description of the problem
+    fn error(&self, message: &str) -> DagParseError {
+        DagParseError::Syntax {
+            line: self.line(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Consumes the current token and fails unless it is `expected`
+    /// # Arguments
+    /// * `expected` - Token required at the current position
+    fn expect(&mut self, expected: Token) -> Result<(), DagParseError> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(self.error(&format!("expected {:?}", expected))),
+        }
+    }
+
+    /// Consumes the current token and returns it as an identifier, fails for any other token
+    fn id(&mut self) -> Result<String, DagParseError> {
+        match self.next() {
+            Some(Token::Id(id)) => Ok(id),
+            _ => Err(self.error("expected a node id")),
+        }
+    }
+
+    /// Parses any number of `[key=value, ...]` lists following the current position
+    fn attributes(&mut self) -> Result<Vec<(String, String)>, DagParseError> {
+        let mut attributes = Vec::new();
+        while self.peek() == Some(&Token::LBracket) {
+            self.position += 1;
+            loop {
+                match self.next() {
+                    Some(Token::RBracket) => break,
+                    Some(Token::Separator) => {}
+                    Some(Token::Id(key)) => {
+                        self.expect(Token::Equals)?;
+                        let value = self.id()?;
+                        attributes.push((key, value));
+                    }
+                    _ => return Err(self.error("malformed attribute list")),
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Quotes a string as a DOT identifier
+/// # Arguments
+/// * `value` - String to quote
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DagParseError, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_dot_export() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_weighted_edge(3, 1, 2.5);
+        dag.add_edge(4, 2);
+        dag.add_edge(4, 3);
+        dag.set_node_attr(4, "label", "tip \"4\"");
+
+        let mut dot = Vec::new();
+        dag.to_dot_ranked(&mut dot).unwrap();
+
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            r#"digraph dag {
+    1;
+    2;
+    3;
+    4 ["label"="tip \"4\""];
+    2 -> 1;
+    3 -> 1 [weight="2.5"];
+    4 -> 2;
+    4 -> 3;
+    { rank=same; 1; }
+    { rank=same; 2; 3; }
+    { rank=same; 4; }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_dot_import() {
+        let dot = r#"
+// exported from another tool
+digraph build {
+    node [shape=box];
+    rankdir = LR;
+    b -> a [weight=2];
+    c -> b -> a;
+    /* attributes */
+    c [label="third node"];
+    subgraph cluster_0 { d -> c; }
+}
+"#;
+
+        let dag = DirectedAcyclicGraph::from_dot(dot.as_bytes()).unwrap();
+        let id = |name: &str| {
+            dag.nodes()
+                .find(|node| dag.node_attr(*node, "name") == Some(name))
+                .unwrap()
+        };
+
+        assert_eq!(dag.nodes().count(), 4);
+        assert_eq!(dag.edges().count(), 3);
+        assert_eq!(dag.origins(), &[id("a")]);
+        assert_eq!(dag.edge_weight(id("b"), id("a")), Some(2.0));
+        assert_eq!(dag.node_attr(id("c"), "label"), Some("third node"));
+        assert_eq!(dag.max_depth(), 4);
+
+        let exhausted = format!("digraph {{ {} -> a }}", usize::MAX);
+        assert!(matches!(
+            DirectedAcyclicGraph::from_dot(exhausted.as_bytes()),
+            Err(DagParseError::InvalidGraph(_))
+        ));
+        let numeric = format!("digraph {{ {} -> 5 }}", usize::MAX);
+        assert!(DirectedAcyclicGraph::from_dot(numeric.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_dot_round_trip() {
+        let dag = sample_dag();
+
+        let mut dot = Vec::new();
+        dag.to_dot_ranked(&mut dot).unwrap();
+        let imported = DirectedAcyclicGraph::from_dot(dot.as_slice()).unwrap();
+
+        let mut edges: Vec<_> = dag.edges().collect();
+        let mut imported_edges: Vec<_> = imported.edges().collect();
+        edges.sort_unstable();
+        imported_edges.sort_unstable();
+        assert_eq!(edges, imported_edges);
+        assert_eq!(imported.origins(), &[1]);
+
+        let error = DirectedAcyclicGraph::from_dot("digraph {\n a -- b\n}".as_bytes());
+        assert!(matches!(error, Err(DagParseError::Syntax { line: 2, .. })));
+    }
+}
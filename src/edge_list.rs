@@ -0,0 +1,133 @@
+use std::io::{BufReader, Read, Write};
+
+use crate::database::{parse_field, DataLines};
+use crate::import::resolve_origins;
+use crate::{DagParseError, DirectedAcyclicGraph};
+
+impl DirectedAcyclicGraph {
+    /// Creates a new Directed Acyclic Graph from a delimited `from,to` edge list such as CSV or TSV
+    /// An optional third column is read as the edge weight
+    /// The first line is treated as a header and skipped if its first two fields are not node ids
+    /// Blank lines and `#` comments are skipped
+    /// Node 1 is the origin if present, otherwise every node without out-references is an origin
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    /// * `delimiter` - Field separator, e.g. `,` for CSV or `\t` for TSV
+    pub fn from_edge_list(
+        reader: impl Read,
+        delimiter: char,
+    ) -> Result<DirectedAcyclicGraph, DagParseError> {
+        let mut lines = DataLines::new(BufReader::new(reader));
+
+        let mut dag = DirectedAcyclicGraph::new();
+        let mut first = true;
+        while lines.next_line()? {
+            let number = lines.number;
+            let mut fields = lines.line.split(delimiter).map(str::trim);
+            let from = fields.next().filter(|field| !field.is_empty());
+            let to = fields.next();
+            if std::mem::take(&mut first) && is_header(from, to) {
+                continue;
+            }
+
+            let from = parse_field(from, number, "source")?;
+            let to = parse_field(to, number, "target")?;
+            if from == to {
+                return Err(DagParseError::Syntax {
+                    line: number,
+                    message: format!("self-referencing edge {}", from),
+                });
+            }
+
+            match fields.next().filter(|field| !field.is_empty()) {
+                None => dag.add_edge(from, to),
+                Some(weight) => {
+                    let weight = weight.parse().map_err(|_| DagParseError::Syntax {
+                        line: number,
+                        message: format!("invalid edge weight {:?}", weight),
+                    })?;
+                    dag.add_weighted_edge(from, to, weight)
+                }
+            };
+        }
+
+        resolve_origins(&mut dag);
+        Ok(dag)
+    }
+
+    /// Writes the edges as a delimited `from,to` edge list with a header line, sorted by edge
+    /// Explicit edge weights are written to a third `weight` column
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    /// * `delimiter` - Field separator, e.g. `,` for CSV or `\t` for TSV
+    pub fn to_edge_list(&self, mut writer: impl Write, delimiter: char) -> std::io::Result<()> {
+        let mut edges: Vec<(usize, usize)> = self.edges().collect();
+        edges.sort_unstable();
+
+        if self.weights.is_empty() {
+            writeln!(writer, "source{}target", delimiter)?;
+        } else {
+            writeln!(writer, "source{0}target{0}weight", delimiter)?;
+        }
+        for (from, to) in edges {
+            match self.weights.get(&(from, to)) {
+                Some(weight) => writeln!(writer, "{1}{0}{2}{0}{3}", delimiter, from, to, weight)?,
+                None => writeln!(writer, "{1}{0}{2}", delimiter, from, to)?,
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+/// Returns if the first line of an edge list is a header rather than an edge
+/// # Arguments
+/// * `from` - First field of the line
+/// * `to` - Second field of the line
+pub(crate) fn is_header(from: Option<&str>, to: Option<&str>) -> bool {
+    let is_id = |field: Option<&str>| field.is_some_and(|field| field.parse::<usize>().is_ok());
+    from.is_some() && to.is_some() && !(is_id(from) && is_id(to))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DagParseError, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_edge_list_import() {
+        let csv = "source,target,weight\n2,1\n3, 1, 0.5\n3,2\n";
+        let dag = DirectedAcyclicGraph::from_edge_list(csv.as_bytes(), ',').unwrap();
+        assert_eq!(dag.edges().count(), 3);
+        assert_eq!(dag.edge_weight(3, 1), Some(0.5));
+        assert_eq!(dag.origins(), &[1]);
+
+        let tsv = "# generated\n11\t10\n\n12\t11\n";
+        let dag = DirectedAcyclicGraph::from_edge_list(tsv.as_bytes(), '\t').unwrap();
+        assert_eq!(dag.origins(), &[10]);
+        assert_eq!(dag.max_depth(), 3);
+
+        let broken = DirectedAcyclicGraph::from_edge_list("2,1\n\n3;1\n".as_bytes(), ',');
+        assert!(matches!(
+            broken,
+            Err(DagParseError::InvalidInteger { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_edge_list_round_trip() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(3, 1);
+        dag.add_weighted_edge(2, 1, 0.5);
+
+        let mut csv = Vec::new();
+        dag.to_edge_list(&mut csv, ',').unwrap();
+        assert_eq!(
+            String::from_utf8(csv.clone()).unwrap(),
+            "source,target,weight\n2,1,0.5\n3,1\n"
+        );
+
+        let parsed = DirectedAcyclicGraph::from_edge_list(csv.as_slice(), ',').unwrap();
+        assert_eq!(parsed, dag);
+        assert_eq!(parsed.edge_weight(2, 1), Some(0.5));
+    }
+}
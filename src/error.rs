@@ -0,0 +1,298 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Errors that can occur while parsing a DAG database
+/// Every variant except `Io` carries the 1-based line number of the offending record
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum DagParseError {
+    /// The underlying reader failed
+    Io(std::io::Error),
+    /// The header line is missing or is not a valid node count
+    BadHeader { line: usize, value: String },
+    /// A node line does not contain the expected field
+    MissingField { line: usize, field: &'static str },
+    /// A field could not be parsed as a node id
+    InvalidInteger { line: usize, value: String },
+    /// The input does not follow the grammar of its format
+    Syntax { line: usize, message: String },
+    /// The input is well formed but does not describe a valid graph
+    InvalidGraph(String),
+    /// A strict parse found a different number of node lines than the header announces
+    NodeCountMismatch { expected: usize, actual: usize },
+    /// A strict parse found a node referencing a parent with a greater id than itself
+    ForwardReference {
+        line: usize,
+        node: usize,
+        parent: usize,
+    },
+    /// A node line lists the same parent twice and duplicates are rejected
+    DuplicateParent {
+        line: usize,
+        node: usize,
+        parent: usize,
+    },
+    /// A strict parse found a parent id outside of 1 through `max`
+    ParentOutOfRange {
+        line: usize,
+        parent: usize,
+        max: usize,
+    },
+}
+
+#[cfg(feature = "std")]
+impl DagParseError {
+    /// Line number the error occurred on, if known
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            DagParseError::Io(_)
+            | DagParseError::InvalidGraph(_)
+            | DagParseError::NodeCountMismatch { .. } => None,
+            DagParseError::BadHeader { line, .. }
+            | DagParseError::MissingField { line, .. }
+            | DagParseError::InvalidInteger { line, .. }
+            | DagParseError::Syntax { line, .. }
+            | DagParseError::ForwardReference { line, .. }
+            | DagParseError::DuplicateParent { line, .. }
+            | DagParseError::ParentOutOfRange { line, .. } => Some(*line),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DagParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DagParseError::Io(err) => write!(f, "I/O error: {}", err),
+            DagParseError::BadHeader { line, value } => {
+                write!(f, "line {}: invalid node count header {:?}", line, value)
+            }
+            DagParseError::MissingField { line, field } => {
+                write!(f, "line {}: missing {}", line, field)
+            }
+            DagParseError::InvalidInteger { line, value } => {
+                write!(f, "line {}: invalid node id {:?}", line, value)
+            }
+            DagParseError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            DagParseError::InvalidGraph(message) => write!(f, "invalid graph: {}", message),
+            DagParseError::NodeCountMismatch { expected, actual } => write!(
+                f,
+                "header announces {} node lines, found {}",
+                expected, actual
+            ),
+            DagParseError::ForwardReference { line, node, parent } => write!(
+                f,
+                "line {}: node {} references parent {} which comes after it",
+                line, node, parent
+            ),
+            DagParseError::DuplicateParent { line, node, parent } => write!(
+                f,
+                "line {}: node {} lists parent {} more than once",
+                line, node, parent
+            ),
+            DagParseError::ParentOutOfRange { line, parent, max } => write!(
+                f,
+                "line {}: parent {} is outside of the node ids 1 through {}",
+                line, parent, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for DagParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DagParseError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for DagParseError {
+    fn from(err: std::io::Error) -> Self {
+        DagParseError::Io(err)
+    }
+}
+
+/// Error returned by `DirectedAcyclicGraph::try_add_edge` when the edge would close a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WouldCreateCycle {
+    /// Start node id of the refused edge
+    pub from: usize,
+    /// Destination node id of the refused edge
+    pub to: usize,
+}
+
+impl fmt::Display for WouldCreateCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "edge {} -> {} would create a cycle", self.from, self.to)
+    }
+}
+
+impl core::error::Error for WouldCreateCycle {}
+
+/// Error returned when a removal would leave nodes without a path to an origin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WouldDisconnect {
+    /// Nodes that would become unreachable, sorted ascending
+    pub nodes: Vec<usize>,
+}
+
+impl fmt::Display for WouldDisconnect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the removal would disconnect nodes {:?} from the origin",
+            self.nodes
+        )
+    }
+}
+
+impl core::error::Error for WouldDisconnect {}
+
+/// Errors returned by `DirectedAcyclicGraph::contract_nodes` and `contract_edge`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractionError {
+    /// The id of the merged node belongs to a node outside the merged set
+    IdInUse(usize),
+    /// A path leaves the merged set through this node and enters it again
+    WouldCreateCycle(usize),
+}
+
+impl fmt::Display for ContractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractionError::IdInUse(node) => write!(f, "node id {} is already in use", node),
+            ContractionError::WouldCreateCycle(node) => {
+                write!(
+                    f,
+                    "the contraction would close a cycle through node {}",
+                    node
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ContractionError {}
+
+/// Errors returned by `DirectedAcyclicGraph::to_compact`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactDagError {
+    /// The graph contains a cycle, a `CompactDag` is stored in topological order
+    Cyclic,
+    /// A node id does not fit in 32 bits
+    IdOutOfRange(usize),
+    /// The edge or reference count does not fit in 32 bits
+    TooLarge,
+}
+
+impl fmt::Display for CompactDagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactDagError::Cyclic => write!(f, "the graph is cyclic"),
+            CompactDagError::IdOutOfRange(node) => {
+                write!(f, "node id {} does not fit in 32 bits", node)
+            }
+            CompactDagError::TooLarge => {
+                write!(f, "the graph has too many edges for 32 bit offsets")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CompactDagError {}
+
+/// Errors that can occur while writing a DAG in the database format
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum DatabaseWriteError {
+    /// The underlying writer failed
+    Io(std::io::Error),
+    /// The database format requires node 1 to be the only origin
+    UnsupportedOrigins(Vec<usize>),
+    /// The database format requires node ids to be exactly 1 through N + 1
+    MissingNode(usize),
+    /// The database format stores at most two parents per node
+    TooManyParents { node: usize, count: usize },
+    /// Every node except the origin needs at least one parent
+    NoParents(usize),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DatabaseWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseWriteError::Io(err) => write!(f, "I/O error: {}", err),
+            DatabaseWriteError::UnsupportedOrigins(origins) => {
+                write!(
+                    f,
+                    "origins {:?} are not supported, the origin must be 1",
+                    origins
+                )
+            }
+            DatabaseWriteError::MissingNode(node) => {
+                write!(f, "node ids are not contiguous, node {} is missing", node)
+            }
+            DatabaseWriteError::TooManyParents { node, count } => {
+                write!(
+                    f,
+                    "node {} has {} parents, at most 2 are supported",
+                    node, count
+                )
+            }
+            DatabaseWriteError::NoParents(node) => write!(f, "node {} has no parents", node),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for DatabaseWriteError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DatabaseWriteError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for DatabaseWriteError {
+    fn from(err: std::io::Error) -> Self {
+        DatabaseWriteError::Io(err)
+    }
+}
+
+/// Errors that can occur while building a DAG with `DagBuilder`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// An edge points from a node to itself
+    SelfReference(usize),
+    /// The edges form a cycle, listed as the nodes along it in edge order
+    Cycle(Vec<usize>),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::SelfReference(node) => write!(f, "node {} references itself", node),
+            BuildError::Cycle(cycle) => write!(f, "the edges form a cycle {:?}", cycle),
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+/// Error returned when parsing an unknown `Metric` name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownMetric(pub String);
+
+impl fmt::Display for UnknownMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown metric {:?}", self.0)
+    }
+}
+
+impl core::error::Error for UnknownMetric {}
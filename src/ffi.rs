@@ -0,0 +1,254 @@
+//! C interface to the graph and its statistics, see `include/dag_statistics.h`
+//! Build the shared library with `cargo rustc --release --lib --features ffi --crate-type cdylib`
+//! Graphs are opaque pointers owned by the caller and released with `dag_free`,
+//! statistics that are undefined for the graph are returned as NaN
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::DirectedAcyclicGraph;
+
+thread_local! {
+    /// Message of the last failed call on this thread, see `dag_last_error`
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Remembers `message` as the last error of this thread
+/// # Arguments
+/// * `message` - Description of the failure
+fn set_last_error(message: impl ToString) {
+    // Interior NUL bytes can not be represented, the message is cut at the first one
+    let mut message = message.to_string().into_bytes();
+    message.truncate(
+        message
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(message.len()),
+    );
+    let message = CString::new(message).ok();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// Returns the graph behind `dag`, None for a null pointer
+/// # Arguments
+/// * `dag` - Pointer returned by `dag_new` or `dag_from_file`
+unsafe fn graph<'a>(dag: *const DirectedAcyclicGraph) -> Option<&'a DirectedAcyclicGraph> {
+    dag.as_ref()
+}
+
+/// Converts an undefined statistic to NaN
+/// # Arguments
+/// * `value` - The statistic
+fn or_nan(value: Option<f64>) -> f64 {
+    value.unwrap_or(f64::NAN)
+}
+
+/// Creates an empty graph with node 1 as its origin
+/// The graph must be released with `dag_free`
+#[no_mangle]
+pub extern "C" fn dag_new() -> *mut DirectedAcyclicGraph {
+    Box::into_raw(Box::new(DirectedAcyclicGraph::new()))
+}
+
+/// Reads a graph from a database file as `DirectedAcyclicGraph::from_path` does
+/// Returns null on failure, the reason is available from `dag_last_error`
+/// The graph must be released with `dag_free`
+/// # Safety
+/// `path` must be null or a NUL terminated string
+/// # Arguments
+/// * `path` - UTF-8 path of the database file
+#[no_mangle]
+pub unsafe extern "C" fn dag_from_file(path: *const c_char) -> *mut DirectedAcyclicGraph {
+    if path.is_null() {
+        set_last_error("path is null");
+        return ptr::null_mut();
+    }
+
+    let path = match CStr::from_ptr(path).to_str() {
+        Ok(path) => path,
+        Err(err) => {
+            set_last_error(format!("path is not valid UTF-8: {}", err));
+            return ptr::null_mut();
+        }
+    };
+    match DirectedAcyclicGraph::from_path(path) {
+        Ok(dag) => Box::into_raw(Box::new(dag)),
+        Err(err) => {
+            set_last_error(err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a graph, null is ignored
+/// # Safety
+/// `dag` must be null or a pointer returned by `dag_new` or `dag_from_file`
+/// that has not been released yet
+/// # Arguments
+/// * `dag` - The graph to release
+#[no_mangle]
+pub unsafe extern "C" fn dag_free(dag: *mut DirectedAcyclicGraph) {
+    if !dag.is_null() {
+        drop(Box::from_raw(dag));
+    }
+}
+
+/// Returns the message of the last failed call on this thread, or null if none failed yet
+/// The string stays valid until the next failing call on the same thread
+#[no_mangle]
+pub extern "C" fn dag_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Inserts an edge and its nodes, see `DirectedAcyclicGraph::add_edge`
+/// Returns if the edge got actually added, false for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+/// * `from` - Start node id
+/// * `to` - Destination node id
+#[no_mangle]
+pub unsafe extern "C" fn dag_add_edge(
+    dag: *mut DirectedAcyclicGraph,
+    from: usize,
+    to: usize,
+) -> bool {
+    dag.as_mut().is_some_and(|dag| dag.add_edge(from, to))
+}
+
+/// Number of nodes, 0 for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_node_count(dag: *const DirectedAcyclicGraph) -> usize {
+    graph(dag).map_or(0, DirectedAcyclicGraph::node_count)
+}
+
+/// Number of edges, 0 for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_edge_count(dag: *const DirectedAcyclicGraph) -> usize {
+    graph(dag).map_or(0, DirectedAcyclicGraph::edge_count)
+}
+
+/// Average depth of the nodes, NaN if undefined or for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_avg_depth(dag: *const DirectedAcyclicGraph) -> f64 {
+    or_nan(graph(dag).and_then(DirectedAcyclicGraph::avg_depth))
+}
+
+/// Longest depth counted in nodes, 0 for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_max_depth(dag: *const DirectedAcyclicGraph) -> usize {
+    graph(dag).map_or(0, DirectedAcyclicGraph::max_depth)
+}
+
+/// Average number of nodes per depth, NaN if undefined or for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_avg_node_per_depth(dag: *const DirectedAcyclicGraph) -> f64 {
+    or_nan(graph(dag).and_then(DirectedAcyclicGraph::avg_node_per_depth))
+}
+
+/// Average number of in-references per node, NaN if undefined or for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_avg_ref(dag: *const DirectedAcyclicGraph) -> f64 {
+    or_nan(graph(dag).and_then(DirectedAcyclicGraph::avg_ref))
+}
+
+/// Average number of out-references per node, NaN if undefined or for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_avg_out_ref(dag: *const DirectedAcyclicGraph) -> f64 {
+    or_nan(graph(dag).and_then(DirectedAcyclicGraph::avg_out_ref))
+}
+
+/// Number of tips, the nodes nothing references, 0 for a null graph
+/// # Safety
+/// `dag` must be null or a live pointer returned by `dag_new` or `dag_from_file`
+/// # Arguments
+/// * `dag` - The graph
+#[no_mangle]
+pub unsafe extern "C" fn dag_tip_count(dag: *const DirectedAcyclicGraph) -> usize {
+    graph(dag).map_or(0, DirectedAcyclicGraph::tip_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+
+    use super::{
+        dag_add_edge, dag_avg_depth, dag_edge_count, dag_free, dag_from_file, dag_last_error,
+        dag_max_depth, dag_new, dag_node_count, dag_tip_count,
+    };
+
+    #[test]
+    fn test_ffi_statistics() {
+        unsafe {
+            let dag = dag_new();
+            assert!(dag_add_edge(dag, 2, 1));
+            assert!(dag_add_edge(dag, 3, 2));
+            assert!(!dag_add_edge(dag, 3, 3));
+            assert_eq!(dag_node_count(dag), 3);
+            assert_eq!(dag_edge_count(dag), 2);
+            assert_eq!(dag_max_depth(dag), 3);
+            assert!((dag_avg_depth(dag) - 1.0).abs() < f64::EPSILON);
+            assert_eq!(dag_tip_count(dag), 1);
+            dag_free(dag);
+
+            assert_eq!(dag_node_count(ptr::null()), 0);
+            assert!(dag_avg_depth(ptr::null()).is_nan());
+            dag_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_ffi_from_file() {
+        let path = std::env::temp_dir().join(format!("dag-ffi-{}.txt", std::process::id()));
+        std::fs::write(&path, "3\n1 1\n1 2\n2 3\n").unwrap();
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let dag = dag_from_file(c_path.as_ptr());
+            std::fs::remove_file(&path).unwrap();
+            assert!(!dag.is_null());
+            assert_eq!(dag_node_count(dag), 4);
+            dag_free(dag);
+
+            assert!(dag_from_file(c_path.as_ptr()).is_null());
+            let error = CStr::from_ptr(dag_last_error()).to_str().unwrap();
+            assert!(error.starts_with("I/O error"), "{}", error);
+        }
+    }
+}
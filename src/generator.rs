@@ -0,0 +1,215 @@
+//! Synthetic DAGs for benchmarks and tests
+//! Every generator takes the random number generator as an argument so seeded runs are reproducible
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::DirectedAcyclicGraph;
+
+/// Seed of `fixture`, fixed so that benchmark runs measure the same graphs
+pub const FIXTURE_SEED: u64 = 0x5eed;
+
+/// Deterministic benchmark fixture, the `uniform` DAG of `nodes` nodes drawn with `FIXTURE_SEED`
+/// # Arguments
+/// * `nodes` - Number of nodes including the origin
+pub fn fixture(nodes: usize) -> DirectedAcyclicGraph {
+    uniform(nodes, &mut StdRng::seed_from_u64(FIXTURE_SEED))
+}
+
+/// `fixture` in the database format, for benchmarking the parser
+/// # Arguments
+/// * `nodes` - Number of nodes including the origin
+pub fn fixture_database(nodes: usize) -> Vec<u8> {
+    let mut database = Vec::new();
+    fixture(nodes)
+        .to_database(&mut database)
+        .expect("uniform graphs are valid databases");
+    database
+}
+
+/// Random DAG shaped like the database format, node 1 is the origin
+/// and every following node references two uniformly chosen earlier nodes,
+/// which collapse into a single edge when both picks are the same
+/// # Arguments
+/// * `nodes` - Number of nodes including the origin
+/// * `rng` - Source of randomness
+pub fn uniform<R: Rng + ?Sized>(nodes: usize, rng: &mut R) -> DirectedAcyclicGraph {
+    let mut dag = DirectedAcyclicGraph::new();
+    if nodes > 0 {
+        dag.add_node(1);
+    }
+    for node in 2..=nodes {
+        dag.add_edge(node, rng.gen_range(1..node));
+        dag.add_edge(node, rng.gen_range(1..node));
+    }
+
+    dag
+}
+
+/// Random DAG grown by Barabási–Albert style preferential attachment, node 1 is the origin
+/// Every following node references up to `parents` distinct earlier nodes,
+/// picking each with probability proportional to its in-reference count plus one
+/// # Arguments
+/// * `nodes` - Number of nodes including the origin
+/// * `parents` - Out-references per node, fewer for the first nodes
+/// * `rng` - Source of randomness
+pub fn preferential_attachment<R: Rng + ?Sized>(
+    nodes: usize,
+    parents: usize,
+    rng: &mut R,
+) -> DirectedAcyclicGraph {
+    let mut dag = DirectedAcyclicGraph::new();
+    if nodes == 0 {
+        return dag;
+    }
+    dag.add_node(1);
+
+    // Every node appears once plus once per in-reference, so uniform picks are preferential
+    let mut urn = vec![1];
+    for node in 2..=nodes {
+        let wanted = parents.min(node - 1);
+        let mut chosen = Vec::with_capacity(wanted);
+        while chosen.len() < wanted {
+            let parent = urn[rng.gen_range(0..urn.len())];
+            if !chosen.contains(&parent) {
+                chosen.push(parent);
+            }
+        }
+
+        for parent in chosen {
+            dag.add_edge(node, parent);
+            urn.push(parent);
+        }
+        urn.push(node);
+    }
+
+    dag
+}
+
+/// Random layered DAG, node 1 is the origin on layer 0 followed by `depth` layers of `width` nodes
+/// Every node references each node of the previous layer with probability `edge_probability`
+/// and at least one of them, so every node reaches the origin
+/// # Arguments
+/// * `width` - Nodes per layer
+/// * `depth` - Number of layers below the origin
+/// * `edge_probability` - Chance of every possible edge between neighboring layers, between 0 and 1
+/// * `rng` - Source of randomness
+pub fn layered<R: Rng + ?Sized>(
+    width: usize,
+    depth: usize,
+    edge_probability: f64,
+    rng: &mut R,
+) -> DirectedAcyclicGraph {
+    let mut dag = DirectedAcyclicGraph::new();
+    dag.add_node(1);
+
+    let mut previous = vec![1];
+    let mut next_id = 2;
+    for _ in 0..depth {
+        let layer: Vec<usize> = (next_id..next_id + width).collect();
+        next_id += width;
+
+        for &node in &layer {
+            let mut referenced = false;
+            for &parent in &previous {
+                if rng.gen_bool(edge_probability) {
+                    dag.add_edge(node, parent);
+                    referenced = true;
+                }
+            }
+            if !referenced {
+                dag.add_edge(node, previous[rng.gen_range(0..previous.len())]);
+            }
+        }
+
+        if !layer.is_empty() {
+            previous = layer;
+        }
+    }
+
+    dag
+}
+
+/// Tangle grown by weighted random walk tip selection, node 1 is the origin
+/// Nodes arrive in rounds of `rate` nodes that all see the tangle as it was before the round,
+/// modelling network delay, and each references the tips picked by two walks,
+/// see `DirectedAcyclicGraph::select_tip`
+/// # Arguments
+/// * `nodes` - Number of nodes including the origin
+/// * `rate` - Nodes arriving per round, at least 1
+/// * `alpha` - Bias of the walks towards heavy nodes, 0 gives unbiased walks
+/// * `rng` - Source of randomness
+pub fn tangle<R: Rng + ?Sized>(
+    nodes: usize,
+    rate: usize,
+    alpha: f64,
+    rng: &mut R,
+) -> DirectedAcyclicGraph {
+    let mut dag = DirectedAcyclicGraph::new();
+    if nodes > 0 {
+        dag.add_node(1);
+    }
+
+    let mut next = 2;
+    while next <= nodes {
+        let weights = dag
+            .cumulative_weights()
+            .expect("tip selection keeps the tangle acyclic");
+        let round = next..(next + rate.max(1)).min(nodes + 1);
+
+        let mut edges = Vec::with_capacity(2 * round.len());
+        for node in round {
+            for _ in 0..2 {
+                let tip = dag
+                    .walk_to_tip(&weights, alpha, rng)
+                    .expect("the origin is always present");
+                edges.push((node, tip));
+            }
+            next = node + 1;
+        }
+        dag.add_edges(edges);
+    }
+
+    dag
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{fixture, fixture_database, layered, preferential_attachment, tangle, uniform};
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_generators() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let dag = uniform(1000, &mut rng);
+        assert_eq!(dag.node_count(), 1000);
+        assert!(dag.is_acyclic() && dag.unreachable_from_origin().is_empty());
+        assert!(dag.max_out_ref() <= 2);
+
+        let dag = preferential_attachment(1000, 3, &mut rng);
+        assert_eq!(dag.edge_count(), 1 + 2 + 997 * 3);
+        assert!(dag.in_degree(1) > 20);
+
+        let dag = layered(10, 20, 0.3, &mut rng);
+        assert_eq!(dag.node_count(), 201);
+        assert_eq!(dag.level_sizes(), [[1].as_ref(), &[10; 20]].concat());
+        assert_eq!(dag.max_depth(), 21);
+
+        // A strong bias makes the walks agree on a few tips, leaving the other arrivals behind
+        let unbiased = tangle(500, 10, 0.0, &mut rng);
+        let biased = tangle(500, 10, 10.0, &mut rng);
+        assert_eq!(biased.node_count(), 500);
+        assert!(biased.tip_count() > unbiased.tip_count());
+    }
+
+    #[test]
+    fn test_fixtures() {
+        assert_eq!(fixture(500), fixture(500));
+        let parsed = DirectedAcyclicGraph::from_read(fixture_database(500).as_slice()).unwrap();
+        assert_eq!(parsed, fixture(500));
+    }
+}
@@ -0,0 +1,430 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{Read, Write};
+
+use quick_xml::escape::{escape, resolve_predefined_entity};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+
+use crate::import::{assign_ids, resolve_origins};
+use crate::{DagParseError, DirectedAcyclicGraph};
+
+/// Element whose `data` children are currently being collected
+enum Owner {
+    Graph,
+    Node(usize),
+    Edge(usize),
+}
+
+/// Node declaration of a GraphML document
+struct NodeElement {
+    name: String,
+    data: Vec<(String, String)>,
+}
+
+/// Edge declaration of a GraphML document
+struct EdgeElement {
+    source: String,
+    target: String,
+    data: Vec<(String, String)>,
+    line: usize,
+}
+
+impl DirectedAcyclicGraph {
+    /// Creates a new Directed Acyclic Graph from a GraphML document
+    /// Node ids are mapped like `from_dot` maps node names
+    /// Node `data` elements become node attributes named after their `key` declaration,
+    /// the edge `weight` data becomes the edge weight and the graph `origins` data,
+    /// a whitespace separated list of node ids, selects the origins
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    pub fn from_graphml(mut reader: impl Read) -> Result<DirectedAcyclicGraph, DagParseError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+
+        let mut xml = Reader::from_str(&source);
+        // Positions are byte offsets that may point past the end or into a character
+        let line_at = |position: u64| {
+            let end = (position as usize).min(source.len());
+            source.as_bytes()[..end]
+                .iter()
+                .filter(|&&byte| byte == b'\n')
+                .count()
+                + 1
+        };
+
+        let mut key_names: HashMap<String, String> = HashMap::new();
+        let mut nodes: Vec<NodeElement> = Vec::new();
+        let mut edges: Vec<EdgeElement> = Vec::new();
+        let mut graph_data: Vec<(String, String)> = Vec::new();
+
+        let mut owner = Owner::Graph;
+        let mut data: Option<(String, String)> = None;
+        loop {
+            let position = xml.buffer_position();
+            let event = xml.read_event().map_err(|err| DagParseError::Syntax {
+                line: line_at(xml.error_position()),
+                message: err.to_string(),
+            })?;
+            let line = line_at(position);
+            let syntax = |message: String| DagParseError::Syntax { line, message };
+            let closed = matches!(event, Event::Empty(_));
+
+            match event {
+                Event::Start(element) | Event::Empty(element) => {
+                    match element.local_name().as_ref() {
+                        "key" => {
+                            let id = attribute(&element, "id", &syntax)?
+                                .ok_or_else(|| syntax("key without id".to_string()))?;
+                            let name = attribute(&element, "attr.name", &syntax)?
+                                .unwrap_or_else(|| id.clone());
+                            key_names.insert(id, name);
+                        }
+                        "graph" => {
+                            if attribute(&element, "edgedefault", &syntax)?.as_deref()
+                                == Some("undirected")
+                            {
+                                return Err(syntax(
+                                    "undirected graphs are not supported".to_string(),
+                                ));
+                            }
+                            owner = Owner::Graph;
+                        }
+                        "node" => {
+                            let name = attribute(&element, "id", &syntax)?
+                                .ok_or_else(|| syntax("node without id".to_string()))?;
+                            nodes.push(NodeElement {
+                                name,
+                                data: Vec::new(),
+                            });
+                            owner = if closed {
+                                Owner::Graph
+                            } else {
+                                Owner::Node(nodes.len() - 1)
+                            };
+                        }
+                        "edge" => {
+                            if attribute(&element, "directed", &syntax)?.as_deref() == Some("false")
+                            {
+                                return Err(syntax(
+                                    "undirected edges are not supported".to_string(),
+                                ));
+                            }
+                            let source = attribute(&element, "source", &syntax)?
+                                .ok_or_else(|| syntax("edge without source".to_string()))?;
+                            let target = attribute(&element, "target", &syntax)?
+                                .ok_or_else(|| syntax("edge without target".to_string()))?;
+                            edges.push(EdgeElement {
+                                source,
+                                target,
+                                data: Vec::new(),
+                                line,
+                            });
+                            owner = if closed {
+                                Owner::Graph
+                            } else {
+                                Owner::Edge(edges.len() - 1)
+                            };
+                        }
+                        "data" if !closed => {
+                            let key = attribute(&element, "key", &syntax)?
+                                .ok_or_else(|| syntax("data without key".to_string()))?;
+                            data = Some((key, String::new()));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some((_, value)) = data.as_mut() {
+                        value.push_str(&text.xml10_content());
+                    }
+                }
+                Event::CData(text) => {
+                    if let Some((_, value)) = data.as_mut() {
+                        value.push_str(&text.into_inner());
+                    }
+                }
+                Event::GeneralRef(reference) => {
+                    if let Some((_, value)) = data.as_mut() {
+                        let resolved = match reference.resolve_char_ref() {
+                            Ok(Some(c)) => c.to_string(),
+                            _ => {
+                                let name = reference.xml10_content();
+                                resolve_predefined_entity(&name)
+                                    .ok_or_else(|| syntax(format!("unknown entity &{};", name)))?
+                                    .to_string()
+                            }
+                        };
+                        value.push_str(&resolved);
+                    }
+                }
+                Event::End(element) => match element.local_name().as_ref() {
+                    "data" => {
+                        if let Some((key, value)) = data.take() {
+                            let name = key_names.get(&key).cloned().unwrap_or(key);
+                            match owner {
+                                Owner::Graph => graph_data.push((name, value)),
+                                Owner::Node(index) => nodes[index].data.push((name, value)),
+                                Owner::Edge(index) => edges[index].data.push((name, value)),
+                            }
+                        }
+                    }
+                    "node" | "edge" => owner = Owner::Graph,
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        let names = nodes.iter().map(|node| node.name.as_str()).chain(
+            edges
+                .iter()
+                .flat_map(|edge| [edge.source.as_str(), edge.target.as_str()]),
+        );
+        let ids = assign_ids(names)?;
+
+        let origins = graph_data
+            .iter()
+            .find(|(name, _)| name == "origins")
+            .map(|(_, origins)| {
+                origins
+                    .split_whitespace()
+                    .map(|origin| {
+                        ids.get(origin).copied().ok_or_else(|| {
+                            DagParseError::InvalidGraph(format!("unknown origin {}", origin))
+                        })
+                    })
+                    .collect::<Result<Vec<usize>, DagParseError>>()
+            })
+            .transpose()?
+            .filter(|origins| !origins.is_empty());
+
+        let mut dag = match &origins {
+            Some(origins) => DirectedAcyclicGraph::with_origins(origins.iter().copied()),
+            None => DirectedAcyclicGraph::new(),
+        };
+        for (name, id) in &ids {
+            dag.nodes.insert(*id);
+            if name.parse::<usize>().is_err() {
+                dag.set_node_attr(*id, "name", name.as_str());
+            }
+        }
+
+        for node in nodes {
+            for (key, value) in node.data {
+                dag.set_node_attr(ids[&node.name], key, value);
+            }
+        }
+
+        for edge in edges {
+            let (from, to) = (ids[&edge.source], ids[&edge.target]);
+            if from == to {
+                return Err(DagParseError::Syntax {
+                    line: edge.line,
+                    message: format!("self-referencing edge {}", edge.source),
+                });
+            }
+
+            let weight = edge.data.iter().find(|(key, _)| key == "weight");
+            match weight {
+                None => dag.add_edge(from, to),
+                Some((_, weight)) => {
+                    let weight =
+                        weight
+                            .trim()
+                            .parse::<f64>()
+                            .map_err(|_| DagParseError::Syntax {
+                                line: edge.line,
+                                message: format!("invalid edge weight {:?}", weight),
+                            })?;
+                    dag.add_weighted_edge(from, to, weight)
+                }
+            };
+        }
+
+        if origins.is_none() {
+            resolve_origins(&mut dag);
+        }
+        Ok(dag)
+    }
+
+    /// Writes the DAG as a GraphML document
+    /// Node attributes become node `data` elements, explicit edge weights become `weight` data
+    /// and the origins are stored in the graph level `origins` data
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    pub fn to_graphml(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+
+        let attribute_names: BTreeSet<&String> = self
+            .attributes
+            .values()
+            .flat_map(|attributes| attributes.keys())
+            .collect();
+        let key_ids: BTreeMap<&String, String> = attribute_names
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| (name, format!("n{}", index)))
+            .collect();
+
+        writeln!(
+            writer,
+            r#"  <key id="origins" for="graph" attr.name="origins" attr.type="string"/>"#
+        )?;
+        if !self.weights.is_empty() {
+            writeln!(
+                writer,
+                r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#
+            )?;
+        }
+        for (name, id) in &key_ids {
+            writeln!(
+                writer,
+                r#"  <key id="{}" for="node" attr.name="{}" attr.type="string"/>"#,
+                id,
+                escape(name.as_str())
+            )?;
+        }
+
+        writeln!(writer, r#"  <graph id="G" edgedefault="directed">"#)?;
+        let origins: Vec<String> = self.origins().iter().map(|o| o.to_string()).collect();
+        writeln!(
+            writer,
+            r#"    <data key="origins">{}</data>"#,
+            origins.join(" ")
+        )?;
+
+        let mut nodes: Vec<usize> = self.nodes().collect();
+        nodes.sort_unstable();
+        for node in nodes {
+            match self.node_attrs(node) {
+                None => writeln!(writer, r#"    <node id="{}"/>"#, node)?,
+                Some(attributes) => {
+                    write!(writer, r#"    <node id="{}">"#, node)?;
+                    let attributes: BTreeMap<_, _> = attributes.iter().collect();
+                    for (key, value) in attributes {
+                        write!(
+                            writer,
+                            r#"<data key="{}">{}</data>"#,
+                            key_ids[key],
+                            escape(value.as_str())
+                        )?;
+                    }
+                    writeln!(writer, "</node>")?;
+                }
+            }
+        }
+
+        let mut edges: Vec<(usize, usize)> = self.edges().collect();
+        edges.sort_unstable();
+        for (from, to) in edges {
+            match self.weights.get(&(from, to)) {
+                None => writeln!(writer, r#"    <edge source="{}" target="{}"/>"#, from, to)?,
+                Some(weight) => writeln!(
+                    writer,
+                    r#"    <edge source="{}" target="{}"><data key="weight">{}</data></edge>"#,
+                    from, to, weight
+                )?,
+            }
+        }
+
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")
+    }
+}
+
+/// Reads and unescapes the attribute `name` of an element
+/// # Arguments
+/// * `element` - Start or empty element
+/// * `name` - Attribute name
+/// * `syntax` - Builds the error reported for malformed attributes
+fn attribute(
+    element: &BytesStart,
+    name: &str,
+    syntax: &impl Fn(String) -> DagParseError,
+) -> Result<Option<String>, DagParseError> {
+    let attribute = element
+        .try_get_attribute(name)
+        .map_err(|err| syntax(err.to_string()))?;
+    attribute
+        .map(|attribute| {
+            attribute
+                .normalized_value(XmlVersion::Implicit1_0)
+                .map(|value| value.into_owned())
+                .map_err(|err| syntax(err.to_string()))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DagParseError, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_graphml_import() {
+        let graphml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="label" attr.type="string"/>
+  <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+  <graph id="G" edgedefault="directed">
+    <node id="root"/>
+    <node id="child"><data key="d0">first &amp; only</data></node>
+    <edge source="child" target="root"><data key="d1">1.5</data></edge>
+  </graph>
+</graphml>"#;
+
+        let dag = DirectedAcyclicGraph::from_graphml(graphml.as_bytes()).unwrap();
+        let id = |name: &str| {
+            dag.nodes()
+                .find(|node| dag.node_attr(*node, "name") == Some(name))
+                .unwrap()
+        };
+
+        assert_eq!(dag.origins(), &[id("root")]);
+        assert_eq!(dag.edge_weight(id("child"), id("root")), Some(1.5));
+        assert_eq!(dag.node_attr(id("child"), "label"), Some("first & only"));
+
+        let broken = "<graphml>\n<graph>\n<node id=\"1\">\n</graph>";
+        assert!(matches!(
+            DirectedAcyclicGraph::from_graphml(broken.as_bytes()),
+            Err(DagParseError::Syntax { line: 4, .. })
+        ));
+
+        let bom = b"\xef\xbb\xbf+";
+        let empty = DirectedAcyclicGraph::from_graphml(&bom[..]).unwrap();
+        assert_eq!(empty.node_count(), 0);
+
+        let exhausted = format!(
+            "<graphml><graph><node id=\"{}\"/><node id=\"a\"/></graph></graphml>",
+            usize::MAX
+        );
+        assert!(matches!(
+            DirectedAcyclicGraph::from_graphml(exhausted.as_bytes()),
+            Err(DagParseError::InvalidGraph(_))
+        ));
+    }
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let mut dag = DirectedAcyclicGraph::with_origins(vec![1, 5]);
+        dag.add_edge(2, 1);
+        dag.add_weighted_edge(3, 2, 0.25);
+        dag.add_edge(3, 5);
+        dag.set_node_attr(3, "label", "<tip>");
+
+        let mut graphml = Vec::new();
+        dag.to_graphml(&mut graphml).unwrap();
+        let parsed = DirectedAcyclicGraph::from_graphml(graphml.as_slice()).unwrap();
+
+        let mut edges: Vec<_> = parsed.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(2, 1), (3, 2), (3, 5)]);
+        assert_eq!(parsed.origins(), &[1, 5]);
+        assert_eq!(parsed.edge_weight(3, 2), Some(0.25));
+        assert_eq!(parsed.node_attr(3, "label"), Some("<tip>"));
+    }
+}
@@ -0,0 +1,104 @@
+use core::hash::Hasher;
+
+/// Hash containers of the crate, the std ones or hashbrown's without the `std` feature
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{hash_map, HashMap, HashSet};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map, HashMap, HashSet};
+
+/// Hasher of the node keyed containers, `FastHasher` with the `fast-hash` feature
+#[cfg(feature = "fast-hash")]
+pub(crate) type NodeHasher = core::hash::BuildHasherDefault<FastHasher>;
+/// Hasher of the node keyed containers, SipHash unless the `fast-hash` feature is enabled
+#[cfg(all(feature = "std", not(feature = "fast-hash")))]
+pub(crate) type NodeHasher = std::collections::hash_map::RandomState;
+/// Hasher of the node keyed containers, hashbrown's default without the `std` feature
+#[cfg(all(not(feature = "std"), not(feature = "fast-hash")))]
+pub(crate) type NodeHasher = hashbrown::DefaultHashBuilder;
+
+/// Map keyed by node ids or edges, see `NodeHasher`
+pub(crate) type NodeMap<K, V> = HashMap<K, V, NodeHasher>;
+
+/// Set of node ids, see `NodeHasher`
+pub(crate) type NodeSet<T> = HashSet<T, NodeHasher>;
+
+/// Multiplicative hasher for integer keys in the style of FxHash, several times faster than
+/// the default SipHash on node ids but without its protection against crafted collisions
+/// Used by the graph containers with the `fast-hash` feature, usable with any `HashMap`
+/// through `BuildHasherDefault<FastHasher>`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FastHasher {
+    hash: u64,
+}
+
+impl FastHasher {
+    /// Odd constant derived from the golden ratio, spreads sequential ids over the high bits
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    /// Mixes one word into the hash
+    /// # Arguments
+    /// * `word` - Word to add
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FastHasher::SEED);
+    }
+}
+
+impl Hasher for FastHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+        for byte in chunks.remainder() {
+            self.add(u64::from(*byte));
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.add(u64::from(value));
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.add(u64::from(value));
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.add(value);
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.add(value as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        // hashbrown takes the control bits from the top, which the multiplication fills well,
+        // and the bucket from the bottom, which the rotation spreads a little further
+        self.hash.rotate_left(26)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, BuildHasherDefault};
+
+    use super::FastHasher;
+
+    #[test]
+    fn test_fast_hasher() {
+        let build = BuildHasherDefault::<FastHasher>::default();
+        assert_eq!(build.hash_one(42usize), build.hash_one(42usize));
+        assert_ne!(build.hash_one(1usize), build.hash_one(2usize));
+        assert_ne!(
+            build.hash_one((1usize, 2usize)),
+            build.hash_one((2usize, 1usize))
+        );
+
+        let hashes: HashSet<u64> = (0..10_000usize)
+            .map(|node| build.hash_one(node) & 0xfff)
+            .collect();
+        assert!(hashes.len() > 3000);
+    }
+}
@@ -0,0 +1,311 @@
+use alloc::{vec, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::hash::HashMap;
+use crate::DirectedAcyclicGraph;
+
+/// In and out degree histograms of a DAG, computed by `DirectedAcyclicGraph::degree_distribution`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DegreeDistribution {
+    /// Histogram of the number of nodes referencing each node
+    pub in_degrees: Histogram,
+    /// Histogram of the number of nodes each node references
+    pub out_degrees: Histogram,
+}
+
+/// Number of occurrences of every value of a per-node metric, such as the depth
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Histogram {
+    /// Occurrences indexed by value
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Builds a histogram from individual values
+    /// # Arguments
+    /// * `values` - Anything that iterates over the measured values
+    pub fn from_values(values: impl IntoIterator<Item = usize>) -> Histogram {
+        let mut counts = Vec::new();
+        for value in values {
+            if counts.len() <= value {
+                counts.resize(value + 1, 0);
+            }
+            counts[value] += 1;
+        }
+
+        Histogram { counts }
+    }
+
+    /// Builds a histogram from the number of occurrences of every value
+    /// # Arguments
+    /// * `counts` - Occurrences keyed by value
+    pub(crate) fn from_counts(counts: &HashMap<usize, usize>) -> Histogram {
+        let len = counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(value, _)| value + 1)
+            .max()
+            .unwrap_or(0);
+        let mut histogram = vec![0; len];
+        for (value, count) in counts {
+            if *count > 0 {
+                histogram[*value] = *count;
+            }
+        }
+
+        Histogram { counts: histogram }
+    }
+
+    /// Occurrences indexed by value, the last entry is never 0
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Occurrences of `value`
+    /// # Arguments
+    /// * `value` - Value to look up
+    pub fn count(&self, value: usize) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Number of measured values
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Smallest value, `None` if the histogram is empty
+    pub fn min(&self) -> Option<usize> {
+        self.counts.iter().position(|count| *count > 0)
+    }
+
+    /// Largest value, `None` if the histogram is empty
+    pub fn max(&self) -> Option<usize> {
+        self.counts.len().checked_sub(1)
+    }
+
+    /// Average value, `None` if the histogram is empty
+    pub fn mean(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        let sum: usize = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(value, count)| value * count)
+            .sum();
+        Some(sum as f64 / total as f64)
+    }
+
+    /// Population variance of the values, `None` if the histogram is empty
+    pub fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let squares: f64 = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(value, count)| (value as f64 - mean).powi(2) * *count as f64)
+            .sum();
+        Some(squares / self.total() as f64)
+    }
+
+    /// Smallest value that at least `p` percent of the values are less than or equal to
+    /// Returns `None` if the histogram is empty
+    /// # Arguments
+    /// * `p` - Percentile between 0 and 100, e.g. 50 for the median or 95 for p95
+    pub fn percentile(&self, p: f64) -> Option<usize> {
+        assert!(
+            (0.0..=100.0).contains(&p),
+            "percentile must be within 0..=100"
+        );
+
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        // Nearest rank method, the rank is 1 based and at least 1
+        let rank = ((p / 100.0 * total as f64).ceil() as usize).max(1);
+        let mut seen = 0;
+        for (value, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(value);
+            }
+        }
+
+        self.max()
+    }
+
+    /// Middle value, same as the 50th percentile
+    pub fn median(&self) -> Option<usize> {
+        self.percentile(50.0)
+    }
+
+    /// Gini coefficient of the values, 0 when every value is equal and approaching 1
+    /// when a single measured item holds the whole sum
+    /// Returns `None` if the histogram is empty
+    pub fn gini(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        // G = 2 * sum(rank * value) / (n * sum(value)) - (n + 1) / n over ascending values
+        let (mut rank, mut sum, mut weighted) = (0.0, 0.0, 0.0);
+        for (value, count) in self.counts.iter().enumerate() {
+            let (value, count) = (value as f64, *count as f64);
+            weighted += value * (rank * count + count * (count + 1.0) / 2.0);
+            sum += value * count;
+            rank += count;
+        }
+        if sum == 0.0 {
+            return Some(0.0);
+        }
+
+        let n = total as f64;
+        Some(2.0 * weighted / (n * sum) - (n + 1.0) / n)
+    }
+
+    /// Shannon entropy in bits of the share of the value sum held by each measured item
+    /// It is `log2(n)` when the sum is spread evenly over all `n` items and 0 when one holds it all
+    /// Returns `None` if the values sum to 0
+    pub fn share_entropy(&self) -> Option<f64> {
+        let sum: usize = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(value, count)| value * count)
+            .sum();
+        if sum == 0 {
+            return None;
+        }
+
+        let entropy = self
+            .counts
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, count)| **count > 0)
+            .map(|(value, count)| {
+                let share = value as f64 / sum as f64;
+                -(*count as f64) * share * share.log2()
+            })
+            .sum();
+        Some(entropy)
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Histogram of the shortest depth of every node, see `avg_depth`
+    /// Nodes without a path to an origin are not counted
+    pub fn depth_distribution(&self) -> Histogram {
+        Histogram::from_values(self.min_depths().into_values())
+    }
+
+    /// Histograms of the in and out degree of every node, counted by edge multiplicity
+    pub fn degree_distribution(&self) -> DegreeDistribution {
+        DegreeDistribution {
+            in_degrees: self.in_reference_distribution(),
+            out_degrees: Histogram::from_values(self.nodes().map(|node| self.out_references(node))),
+        }
+    }
+
+    /// Gini coefficient of the in-references of every node, see `Histogram::gini`
+    /// High values mean references concentrate on a few popular nodes
+    /// Returns `None` for an empty DAG
+    pub fn in_degree_gini(&self) -> Option<f64> {
+        self.in_reference_distribution().gini()
+    }
+
+    /// Entropy of the share of all references each node receives, see `Histogram::share_entropy`
+    /// Returns `None` if the DAG has no edges
+    pub fn in_degree_entropy(&self) -> Option<f64> {
+        self.in_reference_distribution().share_entropy()
+    }
+
+    /// Histogram of the in-references of every node
+    fn in_reference_distribution(&self) -> Histogram {
+        Histogram::from_values(self.nodes().map(|node| self.in_references(node)))
+    }
+
+    /// Shortest depth that `p` percent of the nodes are at or below
+    /// Returns `None` for an empty DAG
+    /// # Arguments
+    /// * `p` - Percentile between 0 and 100, e.g. 50 for the median or 95 for p95
+    pub fn depth_percentile(&self, p: f64) -> Option<usize> {
+        self.depth_distribution().percentile(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph, Histogram};
+
+    #[test]
+    fn test_histogram() {
+        let histogram = Histogram::from_values(vec![3, 1, 1, 4, 1, 5, 9, 2, 6, 5]);
+        assert_eq!(histogram.total(), 10);
+        assert_eq!(histogram.count(1), 3);
+        assert_eq!(histogram.min(), Some(1));
+        assert_eq!(histogram.max(), Some(9));
+        assert_eq!(histogram.mean(), Some(3.7));
+        assert!((histogram.variance().unwrap() - 6.21).abs() < 1e-9);
+        assert_eq!(histogram.median(), Some(3));
+        assert_eq!(histogram.percentile(0.0), Some(1));
+        assert_eq!(histogram.percentile(95.0), Some(9));
+        assert_eq!(Histogram::default().median(), None);
+    }
+
+    #[test]
+    fn test_depth_distribution() {
+        let dag = sample_dag();
+        let distribution = dag.depth_distribution();
+        assert_eq!(distribution.counts(), dag.level_sizes().as_slice());
+        assert_eq!(distribution.mean(), dag.avg_depth());
+        assert_eq!(dag.depth_percentile(50.0), Some(1));
+        assert_eq!(dag.depth_percentile(100.0), Some(2));
+    }
+
+    #[test]
+    fn test_degree_distribution() {
+        let dag = sample_dag();
+        assert_eq!(dag.in_degree(3), 2);
+        assert_eq!(dag.out_degree(3), 2);
+        assert_eq!(dag.in_degree(42), 0);
+
+        let distribution = dag.degree_distribution();
+        assert_eq!(distribution.in_degrees.counts(), &[2, 1, 3]);
+        assert_eq!(distribution.out_degrees.counts(), &[1, 3, 2]);
+        assert_eq!(distribution.out_degrees.min(), Some(dag.min_out_ref()));
+        assert_eq!(distribution.out_degrees.max(), Some(dag.max_out_ref()));
+        assert_eq!(distribution.in_degrees.mean(), dag.avg_ref());
+    }
+
+    #[test]
+    fn test_inequality() {
+        let even = Histogram::from_values(vec![2, 2, 2, 2]);
+        assert_eq!(even.gini(), Some(0.0));
+        assert_eq!(even.share_entropy(), Some(2.0));
+
+        let concentrated = Histogram::from_values(vec![0, 0, 0, 8]);
+        assert_eq!(concentrated.gini(), Some(0.75));
+        assert_eq!(concentrated.share_entropy(), Some(0.0));
+
+        let skewed = Histogram::from_values(vec![1, 2, 3, 4]);
+        assert!((skewed.gini().unwrap() - 0.25).abs() < 1e-12);
+        assert_eq!(Histogram::default().gini(), None);
+        assert_eq!(Histogram::from_values(vec![0, 0]).share_entropy(), None);
+
+        let mut star = DirectedAcyclicGraph::new();
+        for node in 2..=5 {
+            star.add_edge(node, 1);
+        }
+        assert_eq!(star.in_degree_gini(), Some(0.8));
+        assert_eq!(star.in_degree_entropy(), Some(0.0));
+    }
+}
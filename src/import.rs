@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::{DagParseError, DirectedAcyclicGraph};
+
+/// Maps node names of interchange formats to node ids
+/// Names that are valid integers keep their value as id,
+/// every other name gets a fresh id above the largest integer name in first seen order
+/// Fresh ids start at 2 so that only a literal `1` can become the default origin
+/// Fails if an integer name is so large that no fresh id is left above it
+/// # Arguments
+/// * `names` - Every node name of the input, duplicates are allowed
+pub(crate) fn assign_ids<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+) -> Result<HashMap<String, usize>, DagParseError> {
+    let names: Vec<&str> = names.into_iter().collect();
+
+    let mut ids = HashMap::new();
+    for name in &names {
+        if let Ok(id) = name.parse::<usize>() {
+            ids.insert(name.to_string(), id);
+        }
+    }
+
+    let mut next = ids
+        .values()
+        .max()
+        .map_or(Some(2), |max| max.max(&1).checked_add(1));
+    for name in names {
+        if !ids.contains_key(name) {
+            let id = next.ok_or_else(|| {
+                DagParseError::InvalidGraph(format!(
+                    "no node id left for {:?} above the largest integer node name",
+                    name
+                ))
+            })?;
+            ids.insert(name.to_string(), id);
+            next = id.checked_add(1);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Picks the origins of an imported graph
+/// Node 1 stays the origin if it exists, otherwise every node without out-references becomes an origin
+/// # Arguments
+/// * `dag` - Freshly imported DAG
+pub(crate) fn resolve_origins(dag: &mut DirectedAcyclicGraph) {
+    if dag.nodes.is_empty() || dag.nodes.contains(&1) {
+        return;
+    }
+
+    let mut sinks: Vec<usize> = dag
+        .nodes()
+        .filter(|node| dag.neighbors(*node).is_empty())
+        .collect();
+    sinks.sort_unstable();
+    if !sinks.is_empty() {
+        dag.origins = sinks;
+    }
+}
@@ -0,0 +1,260 @@
+use alloc::collections::VecDeque;
+use alloc::vec;
+
+use crate::hash::HashMap;
+use crate::{average, DirectedAcyclicGraph, WouldCreateCycle};
+
+/// A DAG paired with its depth tables, which are updated in place as edges are inserted
+/// so the statistics stay available without recomputation, e.g. for streaming workloads
+/// Inserting an edge only revisits the nodes whose depth actually changes,
+/// removing an edge recomputes the tables from scratch
+pub struct IncrementalStats {
+    dag: DirectedAcyclicGraph,
+    /// Shortest depth of every node that can reach an origin
+    min_depths: HashMap<usize, usize>,
+    /// Longest depth of every node that can reach an origin
+    max_depths: HashMap<usize, usize>,
+    /// Sum of all shortest depths
+    total_depth: usize,
+    /// Largest longest depth, `None` while no node reaches an origin
+    max_depth: Option<usize>,
+    /// Number of nodes no other node references
+    tip_count: usize,
+}
+
+impl Default for IncrementalStats {
+    fn default() -> Self {
+        IncrementalStats::new()
+    }
+}
+
+impl IncrementalStats {
+    /// Returns an empty DAG with node 1 as its origin
+    pub fn new() -> IncrementalStats {
+        IncrementalStats::from_dag(DirectedAcyclicGraph::new()).unwrap()
+    }
+
+    /// Wraps an existing DAG, computing its depth tables once
+    /// Returns `None` if the DAG is cyclic
+    /// # Arguments
+    /// * `dag` - DAG to keep the statistics of
+    pub fn from_dag(dag: DirectedAcyclicGraph) -> Option<IncrementalStats> {
+        dag.topological_order()?;
+
+        let mut stats = IncrementalStats {
+            dag,
+            min_depths: HashMap::new(),
+            max_depths: HashMap::new(),
+            total_depth: 0,
+            max_depth: None,
+            tip_count: 0,
+        };
+        stats.recompute();
+        Some(stats)
+    }
+
+    /// The wrapped DAG
+    pub fn dag(&self) -> &DirectedAcyclicGraph {
+        &self.dag
+    }
+
+    /// Unwraps the DAG, dropping the depth tables
+    pub fn into_inner(self) -> DirectedAcyclicGraph {
+        self.dag
+    }
+
+    /// Inserts a new edge and updates the statistics unless it would introduce a cycle
+    /// Returns if the edge got actually added to DAG, or an error if `to` already reaches `from`
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn add_edge(&mut self, from: usize, to: usize) -> Result<bool, WouldCreateCycle> {
+        let from_is_new = !self.dag.nodes.contains(&from);
+        let to_is_new = !self.dag.nodes.contains(&to);
+        let to_was_tip = !to_is_new && self.dag.predecessors(to).is_empty();
+
+        if !self.dag.try_add_edge(from, to)? {
+            return Ok(false);
+        }
+
+        if from_is_new {
+            self.tip_count += 1;
+        }
+        if to_was_tip {
+            self.tip_count -= 1;
+        }
+        if to_is_new && self.dag.is_origin(to) {
+            self.min_depths.insert(to, 0);
+            self.max_depths.insert(to, 0);
+            self.max_depth = self.max_depth.max(Some(0));
+        }
+        if from_is_new && self.dag.is_origin(from) {
+            self.min_depths.insert(from, 0);
+            self.max_depths.insert(from, 0);
+            self.max_depth = self.max_depth.max(Some(0));
+        }
+
+        if let Some(depth) = self.min_depths.get(&to).copied() {
+            self.lower_min_depth(from, depth + 1);
+        }
+        if let Some(depth) = self.max_depths.get(&to).copied() {
+            self.raise_max_depth(from, depth + 1);
+        }
+
+        Ok(true)
+    }
+
+    /// Removes the specified edge, see `DirectedAcyclicGraph::remove_edge`
+    /// Depths can grow or become undefined, so the tables are recomputed from scratch
+    /// Returns if the edge got actually removed from DAG
+    /// # Arguments
+    /// * `from` - Starting node id
+    /// * `to` - Destination node id
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        if !self.dag.remove_edge(from, to) {
+            return false;
+        }
+
+        self.recompute();
+        true
+    }
+
+    /// Average shortest depth, see `DirectedAcyclicGraph::avg_depth`
+    pub fn avg_depth(&self) -> Option<f64> {
+        average(self.total_depth as f64, self.min_depths.len())
+    }
+
+    /// Longest depth counted in nodes, see `DirectedAcyclicGraph::max_depth`
+    pub fn max_depth(&self) -> usize {
+        self.max_depth.map_or(0, |depth| depth + 1)
+    }
+
+    /// Average in-reference per node, see `DirectedAcyclicGraph::avg_ref`
+    pub fn avg_ref(&self) -> Option<f64> {
+        average(self.dag.reference_count() as f64, self.dag.nodes.len())
+    }
+
+    /// Average out-reference per node, see `DirectedAcyclicGraph::avg_out_ref`
+    pub fn avg_out_ref(&self) -> Option<f64> {
+        average(self.dag.reference_count() as f64, self.dag.nodes.len())
+    }
+
+    /// Number of tips, see `DirectedAcyclicGraph::tip_count`
+    pub fn tip_count(&self) -> usize {
+        self.tip_count
+    }
+
+    /// Shortest depth of `node`, `None` if it has no path to an origin
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn min_depth_of(&self, node: usize) -> Option<usize> {
+        self.min_depths.get(&node).copied()
+    }
+
+    /// Longest depth of `node`, `None` if it has no path to an origin
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn depth_of(&self, node: usize) -> Option<usize> {
+        self.max_depths.get(&node).copied()
+    }
+
+    /// Rebuilds every table from the wrapped DAG
+    fn recompute(&mut self) {
+        let order = self
+            .dag
+            .topological_order()
+            .expect("edge insertion keeps the graph acyclic");
+        self.min_depths = self.dag.min_depths();
+        self.max_depths = self.dag.max_depths(&order);
+        self.total_depth = self.min_depths.values().sum();
+        self.max_depth = self.max_depths.values().max().copied();
+        self.tip_count = self.dag.tip_count();
+    }
+
+    /// Lowers the shortest depth of `node` and everything referencing it, breadth first
+    /// # Arguments
+    /// * `node` - Node id that gained a shorter path
+    /// * `depth` - Length of that path
+    fn lower_min_depth(&mut self, node: usize, depth: usize) {
+        let mut queue = VecDeque::new();
+        queue.push_back((node, depth));
+        while let Some((node, depth)) = queue.pop_front() {
+            match self.min_depths.get(&node) {
+                Some(current) if *current <= depth => continue,
+                Some(current) => self.total_depth -= current,
+                None => {}
+            }
+            self.min_depths.insert(node, depth);
+            self.total_depth += depth;
+
+            for predecessor in self.dag.predecessors(node) {
+                queue.push_back((*predecessor, depth + 1));
+            }
+        }
+    }
+
+    /// Raises the longest depth of `node` and everything referencing it
+    /// # Arguments
+    /// * `node` - Node id that gained a longer path
+    /// * `depth` - Length of that path
+    fn raise_max_depth(&mut self, node: usize, depth: usize) {
+        let mut stack = vec![(node, depth)];
+        while let Some((node, depth)) = stack.pop() {
+            if self.dag.is_origin(node) {
+                continue;
+            }
+            if self
+                .max_depths
+                .get(&node)
+                .is_some_and(|current| *current >= depth)
+            {
+                continue;
+            }
+            self.max_depths.insert(node, depth);
+            self.max_depth = self.max_depth.max(Some(depth));
+
+            for predecessor in self.dag.predecessors(node) {
+                stack.push((*predecessor, depth + 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DirectedAcyclicGraph, IncrementalStats};
+
+    #[test]
+    fn test_incremental_stats_match_recomputation() {
+        let mut stats = IncrementalStats::new();
+        let mut edges = Vec::new();
+        for node in 2..300 {
+            edges.push((node, node / 2));
+            edges.push((node, node - 1));
+        }
+        // Late edges shortcut and lengthen paths of nodes that already exist
+        edges.push((250, 1));
+        edges.push((1000, 1));
+        for node in 1001..1010 {
+            edges.push((node, node - 1));
+        }
+        edges.push((2, 1009));
+
+        for (from, to) in edges {
+            stats.add_edge(from, to).unwrap();
+
+            let statistics = stats.dag().statistics();
+            assert_eq!(stats.avg_depth(), statistics.avg_depth);
+            assert_eq!(stats.max_depth(), statistics.max_depth);
+            assert_eq!(stats.avg_ref(), statistics.avg_ref);
+            assert_eq!(stats.tip_count(), statistics.tip_count);
+        }
+        assert!(stats.add_edge(1, 5).is_err());
+        assert_eq!(stats.add_edge(2, 1), Ok(false));
+
+        stats.remove_edge(250, 1);
+        let dag: DirectedAcyclicGraph = stats.into_inner();
+        assert_eq!(dag.min_depth_of(250), Some(7));
+        assert_eq!(dag.depth_of(2), Some(11));
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::import::resolve_origins;
+use crate::DirectedAcyclicGraph;
+
+/// Converts to a petgraph graph whose node weights are the node ids, added in ascending order
+/// Edges keep their direction from a node to its parent, repeated edges are added once per
+/// occurrence, weights and attributes are dropped
+impl From<&DirectedAcyclicGraph> for DiGraph<usize, ()> {
+    fn from(dag: &DirectedAcyclicGraph) -> Self {
+        let mut nodes: Vec<usize> = dag.nodes().collect();
+        nodes.sort_unstable();
+
+        let mut graph = DiGraph::with_capacity(nodes.len(), dag.reference_count());
+        let indices: HashMap<usize, NodeIndex> = nodes
+            .iter()
+            .map(|node| (*node, graph.add_node(*node)))
+            .collect();
+        for &from in &nodes {
+            let mut parents = dag.neighbors(from).to_vec();
+            parents.sort_unstable();
+            for to in parents {
+                let multiplicity = dag.edge_multiplicity(from, to).unwrap_or(1);
+                for _ in 0..multiplicity {
+                    graph.add_edge(indices[&from], indices[&to], ());
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+/// Converts from a petgraph graph whose node weights are the node ids
+/// Nodes sharing a weight are merged, parallel edges become an edge with a multiplicity
+/// Node 1 is the origin if it exists, otherwise every node without out-edges
+impl From<&DiGraph<usize, ()>> for DirectedAcyclicGraph {
+    fn from(graph: &DiGraph<usize, ()>) -> Self {
+        let mut multiplicities: HashMap<(usize, usize), usize> = HashMap::new();
+        for edge in graph.edge_references() {
+            let from = graph[edge.source()];
+            let to = graph[edge.target()];
+            *multiplicities.entry((from, to)).or_insert(0) += 1;
+        }
+
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.reserve(graph.node_count());
+        for node in graph.node_weights() {
+            dag.add_node(*node);
+        }
+        for ((from, to), multiplicity) in multiplicities {
+            dag.add_edge_with_multiplicity(from, to, multiplicity);
+        }
+        resolve_origins(&mut dag);
+
+        dag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::algo::{is_cyclic_directed, toposort};
+    use petgraph::graph::DiGraph;
+
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_petgraph_round_trip() {
+        let mut dag = sample_dag();
+        dag.add_edge_with_multiplicity(6, 4, 2);
+        let graph = DiGraph::<usize, ()>::from(&dag);
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 9);
+        assert!(!is_cyclic_directed(&graph));
+        let order: Vec<usize> = toposort(&graph, None)
+            .unwrap()
+            .into_iter()
+            .map(|index| graph[index])
+            .collect();
+        assert_eq!(order.last(), Some(&1));
+
+        let converted = DirectedAcyclicGraph::from(&graph);
+        assert_eq!(converted.statistics(), dag.statistics());
+        assert_eq!(converted.edge_multiplicity(6, 4), Some(2));
+        assert_eq!(converted.origins(), &[1]);
+
+        let mut shifted = DiGraph::<usize, ()>::new();
+        let a = shifted.add_node(10);
+        let b = shifted.add_node(11);
+        shifted.add_edge(b, a, ());
+        assert_eq!(DirectedAcyclicGraph::from(&shifted).origins(), &[10]);
+    }
+}
@@ -0,0 +1,172 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::hash::{HashMap, HashSet};
+use crate::DirectedAcyclicGraph;
+
+impl DirectedAcyclicGraph {
+    /// Returns if both DAGs have the same shape regardless of node ids
+    /// Origins, weights and attributes are ignored
+    /// Uses backtracking search, which is exponential in the worst case and meant for small DAGs
+    /// # Arguments
+    /// * `other` - DAG to compare with
+    pub fn is_isomorphic_to(&self, other: &DirectedAcyclicGraph) -> bool {
+        if self.nodes.len() != other.nodes.len() || self.edge_count != other.edge_count {
+            return false;
+        }
+
+        let degrees = |dag: &DirectedAcyclicGraph| {
+            let mut degrees: Vec<(usize, usize)> = dag
+                .nodes()
+                .map(|node| (dag.in_degree(node), dag.out_degree(node)))
+                .collect();
+            degrees.sort_unstable();
+            degrees
+        };
+        if degrees(self) != degrees(other) {
+            return false;
+        }
+
+        // Visiting nodes breadth first keeps every newly mapped node adjacent to
+        // already mapped ones, so inconsistent candidates are pruned early
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut seen = HashSet::new();
+        for component in self.connected_components() {
+            let mut queue: VecDeque<usize> = VecDeque::new();
+            queue.push_back(component[0]);
+            seen.insert(component[0]);
+            while let Some(node) = queue.pop_front() {
+                order.push(node);
+                for &next in self.neighbors(node).iter().chain(self.predecessors(node)) {
+                    if seen.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        let mut candidates: Vec<usize> = other.nodes().collect();
+        candidates.sort_unstable();
+        let mut matcher = Matcher {
+            left: self,
+            right: other,
+            order,
+            candidates,
+            mapping: HashMap::new(),
+            used: HashSet::new(),
+        };
+        matcher.extend(0)
+    }
+}
+
+/// Backtracking state of `is_isomorphic_to`
+struct Matcher<'a> {
+    left: &'a DirectedAcyclicGraph,
+    right: &'a DirectedAcyclicGraph,
+    /// Order the nodes of `left` are mapped in
+    order: Vec<usize>,
+    /// Nodes of `right` in the order they are tried
+    candidates: Vec<usize>,
+    /// Partial mapping from `left` to `right`
+    mapping: HashMap<usize, usize>,
+    /// Nodes of `right` that are already mapped to
+    used: HashSet<usize>,
+}
+
+impl Matcher<'_> {
+    /// Tries to map the remaining nodes starting at `order[index]`
+    /// Returns if a complete mapping was found
+    /// # Arguments
+    /// * `index` - Position in `order` of the next node to map
+    fn extend(&mut self, index: usize) -> bool {
+        let node = match self.order.get(index) {
+            Some(node) => *node,
+            None => return true,
+        };
+
+        for i in 0..self.candidates.len() {
+            let candidate = self.candidates[i];
+            if self.used.contains(&candidate) || !self.is_feasible(node, candidate) {
+                continue;
+            }
+
+            self.mapping.insert(node, candidate);
+            self.used.insert(candidate);
+            if self.extend(index + 1) {
+                return true;
+            }
+            self.mapping.remove(&node);
+            self.used.remove(&candidate);
+        }
+
+        false
+    }
+
+    /// Returns if mapping `node` to `candidate` agrees with the mapping so far
+    /// # Arguments
+    /// * `node` - Node of `left`
+    /// * `candidate` - Node of `right`
+    fn is_feasible(&self, node: usize, candidate: usize) -> bool {
+        if self.left.in_degree(node) != self.right.in_degree(candidate)
+            || self.left.out_degree(node) != self.right.out_degree(candidate)
+        {
+            return false;
+        }
+
+        let directions: [(&[usize], &[usize]); 2] = [
+            (self.left.neighbors(node), self.right.neighbors(candidate)),
+            (
+                self.left.predecessors(node),
+                self.right.predecessors(candidate),
+            ),
+        ];
+        directions.iter().all(|(left, right)| {
+            let mut mapped = 0;
+            for adjacent in left.iter() {
+                if let Some(image) = self.mapping.get(adjacent) {
+                    if !right.contains(image) {
+                        return false;
+                    }
+                    mapped += 1;
+                }
+            }
+            mapped
+                == right
+                    .iter()
+                    .filter(|&adjacent| self.used.contains(adjacent))
+                    .count()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_isomorphism() {
+        let dag = sample_dag();
+
+        // The same shape with every id shifted and origin 1 swapped with node 6
+        let relabel = |node: usize| {
+            if node == 1 {
+                16
+            } else if node == 6 {
+                11
+            } else {
+                node + 10
+            }
+        };
+        let mut relabeled = DirectedAcyclicGraph::with_origin(16);
+        for (from, to) in dag.edges() {
+            relabeled.add_edge(relabel(from), relabel(to));
+        }
+        assert!(dag.is_isomorphic_to(&relabeled));
+        assert!(dag != relabeled);
+
+        let mut bent = sample_dag();
+        bent.remove_edge(4, 2);
+        bent.add_edge(4, 3);
+        assert!(!dag.is_isomorphic_to(&bent));
+    }
+}
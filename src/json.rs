@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use serde_json::{json, Map, Value};
+
+use crate::import::{assign_ids, resolve_origins};
+use crate::{DagParseError, DirectedAcyclicGraph};
+
+impl DirectedAcyclicGraph {
+    /// Creates a new Directed Acyclic Graph from node-link JSON as produced by networkx and D3
+    /// Edges are read from `links`, or from `edges` for newer networkx versions
+    /// Integer and string ids are mapped like `from_dot` maps node names
+    /// Extra node fields become node attributes and the `weight` link field becomes the edge weight
+    /// The origins are read from `graph.origins` when present
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    pub fn from_json(reader: impl Read) -> Result<DirectedAcyclicGraph, DagParseError> {
+        let document: Value = serde_json::from_reader(reader).map_err(|err| {
+            if err.is_io() {
+                DagParseError::Io(err.into())
+            } else {
+                DagParseError::Syntax {
+                    line: err.line(),
+                    message: err.to_string(),
+                }
+            }
+        })?;
+
+        if document.get("directed") == Some(&Value::Bool(false)) {
+            return Err(invalid("undirected graphs are not supported"));
+        }
+
+        let nodes = match document.get("nodes") {
+            Some(Value::Array(nodes)) => nodes.as_slice(),
+            None => &[],
+            Some(_) => return Err(invalid("`nodes` must be an array")),
+        };
+        let links = match document.get("links").or_else(|| document.get("edges")) {
+            Some(Value::Array(links)) => links.as_slice(),
+            None => &[],
+            Some(_) => return Err(invalid("`links` must be an array")),
+        };
+
+        let mut names = Vec::with_capacity(nodes.len() + 2 * links.len());
+        for node in nodes {
+            names.push(id_name(node.get("id"), "node id")?);
+        }
+        for link in links {
+            names.push(id_name(link.get("source"), "link source")?);
+            names.push(id_name(link.get("target"), "link target")?);
+        }
+        let ids = assign_ids(names.iter().map(String::as_str))?;
+
+        let mut dag = match document.pointer("/graph/origins") {
+            Some(Value::Array(origins)) if !origins.is_empty() => {
+                let origins = origins
+                    .iter()
+                    .map(|origin| {
+                        let name = id_name(Some(origin), "origin")?;
+                        ids.get(&name)
+                            .copied()
+                            .ok_or_else(|| invalid(&format!("unknown origin {}", name)))
+                    })
+                    .collect::<Result<Vec<usize>, DagParseError>>()?;
+                DirectedAcyclicGraph::with_origins(origins)
+            }
+            _ => DirectedAcyclicGraph::new(),
+        };
+
+        for (name, id) in &ids {
+            dag.nodes.insert(*id);
+            if name.parse::<usize>().is_err() {
+                dag.set_node_attr(*id, "name", name.as_str());
+            }
+        }
+
+        for node in nodes {
+            let id = ids[&id_name(node.get("id"), "node id")?];
+            if let Value::Object(fields) = node {
+                for (key, value) in fields.iter().filter(|(key, _)| *key != "id") {
+                    dag.set_node_attr(id, key.as_str(), attribute_value(value));
+                }
+            }
+        }
+
+        for link in links {
+            let from = ids[&id_name(link.get("source"), "link source")?];
+            let to = ids[&id_name(link.get("target"), "link target")?];
+            if from == to {
+                return Err(invalid(&format!("self-referencing link {}", from)));
+            }
+
+            match link.get("weight") {
+                None => dag.add_edge(from, to),
+                Some(weight) => {
+                    let weight = weight
+                        .as_f64()
+                        .ok_or_else(|| invalid(&format!("invalid link weight {}", weight)))?;
+                    dag.add_weighted_edge(from, to, weight)
+                }
+            };
+        }
+
+        if document.pointer("/graph/origins").is_none() {
+            resolve_origins(&mut dag);
+        }
+        Ok(dag)
+    }
+
+    /// Writes the DAG as node-link JSON readable by networkx and D3
+    /// Node attributes become node fields, explicit edge weights become `weight` link fields
+    /// and the origins are stored in `graph.origins`
+    /// # Arguments
+    /// * `writer` - Anything that implements `Write`
+    pub fn to_json(&self, writer: impl Write) -> std::io::Result<()> {
+        let mut nodes: Vec<usize> = self.nodes().collect();
+        nodes.sort_unstable();
+        let nodes: Vec<Value> = nodes
+            .into_iter()
+            .map(|node| {
+                let mut fields = Map::new();
+                fields.insert("id".to_string(), json!(node));
+                if let Some(attributes) = self.node_attrs(node) {
+                    let attributes: BTreeMap<_, _> = attributes.iter().collect();
+                    for (key, value) in attributes {
+                        fields.insert(key.clone(), json!(value));
+                    }
+                }
+                Value::Object(fields)
+            })
+            .collect();
+
+        let mut edges: Vec<(usize, usize)> = self.edges().collect();
+        edges.sort_unstable();
+        let links: Vec<Value> = edges
+            .into_iter()
+            .map(|(from, to)| match self.weights.get(&(from, to)) {
+                Some(weight) => json!({ "source": from, "target": to, "weight": weight }),
+                None => json!({ "source": from, "target": to }),
+            })
+            .collect();
+
+        let document = json!({
+            "directed": true,
+            "multigraph": false,
+            "graph": { "origins": self.origins() },
+            "nodes": nodes,
+            "links": links,
+        });
+
+        serde_json::to_writer(writer, &document)?;
+        Ok(())
+    }
+}
+
+/// Converts a node-link id to the name used for id assignment
+/// # Arguments
+/// * `id` - The id field, `None` if it is missing
+/// * `field` - Human readable field name used for error reporting
+fn id_name(id: Option<&Value>, field: &str) -> Result<String, DagParseError> {
+    match id {
+        Some(Value::String(name)) => Ok(name.clone()),
+        Some(Value::Number(number)) if number.is_u64() => Ok(number.to_string()),
+        Some(value) => Err(invalid(&format!("invalid {} {}", field, value))),
+        None => Err(invalid(&format!("missing {}", field))),
+    }
+}
+
+/// Converts a JSON value to an attribute string, strings are stored without quotes
+fn attribute_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn invalid(message: &str) -> DagParseError {
+    DagParseError::InvalidGraph(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DagParseError, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_node_link_import() {
+        let json = r#"{
+            "directed": true,
+            "multigraph": false,
+            "graph": {},
+            "nodes": [{"id": "a"}, {"id": "b", "color": "red", "size": 3}, {"id": "c"}],
+            "links": [
+                {"source": "b", "target": "a", "weight": 2.5},
+                {"source": "c", "target": "b"},
+                {"source": "c", "target": "a"}
+            ]
+        }"#;
+
+        let dag = DirectedAcyclicGraph::from_json(json.as_bytes()).unwrap();
+        let id = |name: &str| {
+            dag.nodes()
+                .find(|node| dag.node_attr(*node, "name") == Some(name))
+                .unwrap()
+        };
+
+        assert_eq!(dag.edges().count(), 3);
+        assert_eq!(dag.origins(), &[id("a")]);
+        assert_eq!(dag.edge_weight(id("b"), id("a")), Some(2.5));
+        assert_eq!(dag.node_attr(id("b"), "color"), Some("red"));
+        assert_eq!(dag.node_attr(id("b"), "size"), Some("3"));
+
+        let broken = DirectedAcyclicGraph::from_json("{\n\"nodes\": [\n".as_bytes());
+        assert!(matches!(broken, Err(DagParseError::Syntax { .. })));
+
+        let exhausted = format!(
+            r#"{{"nodes": [{{"id": {}}}, {{"id": "a"}}], "links": []}}"#,
+            usize::MAX
+        );
+        assert!(matches!(
+            DirectedAcyclicGraph::from_json(exhausted.as_bytes()),
+            Err(DagParseError::InvalidGraph(_))
+        ));
+    }
+
+    #[test]
+    fn test_node_link_round_trip() {
+        let mut dag = DirectedAcyclicGraph::with_origin(10);
+        dag.add_edge(11, 10);
+        dag.add_weighted_edge(12, 11, 0.5);
+        dag.set_node_attr(12, "label", "tip");
+
+        let mut json = Vec::new();
+        dag.to_json(&mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json.clone()).unwrap(),
+            r#"{"directed":true,"graph":{"origins":[10]},"links":[{"source":11,"target":10},{"source":12,"target":11,"weight":0.5}],"multigraph":false,"nodes":[{"id":10},{"id":11},{"id":12,"label":"tip"}]}"#
+        );
+
+        let parsed = DirectedAcyclicGraph::from_json(json.as_slice()).unwrap();
+        assert_eq!(parsed.origins(), &[10]);
+        assert_eq!(parsed.edge_weight(12, 11), Some(0.5));
+        assert_eq!(parsed.node_attr(12, "label"), Some("tip"));
+    }
+}
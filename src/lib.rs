@@ -1,164 +1,453 @@
-use std::collections::{HashMap, HashSet};
-use std::io::{BufReader, BufRead};
-use std::io::Read;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::iter::FromIterator;
+
+#[cfg(feature = "tokio")]
+mod async_read;
+mod bottleneck;
+mod builder;
+mod centrality;
+mod compact;
+mod components;
+#[cfg(feature = "std")]
+mod compression;
+mod contraction;
+#[cfg(feature = "std")]
+mod database;
+mod depth;
+mod diff;
+mod distance;
+mod dominators;
+#[cfg(feature = "std")]
+mod dot;
+#[cfg(feature = "std")]
+mod edge_list;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "generator")]
+pub mod generator;
+#[cfg(feature = "graphml")]
+mod graphml;
+mod hash;
+mod histogram;
+#[cfg(feature = "std")]
+mod import;
+mod incremental;
+#[cfg(feature = "petgraph")]
+mod interop;
+mod isomorphism;
+#[cfg(feature = "json")]
+mod json;
+mod memory;
+mod merge;
+mod paths;
+mod reachability;
+mod removal;
+mod report;
+mod sampling;
+mod schedule;
+#[cfg(feature = "serde")]
+mod serialization;
+mod statistics;
+mod subgraph;
+mod tangle;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod tips;
+mod transitive;
+mod traversal;
+mod validate;
+mod view;
+mod weighted;
+
+pub use builder::DagBuilder;
+pub use compact::CompactDag;
+#[cfg(feature = "std")]
+pub use compression::decompress;
+#[cfg(feature = "std")]
+pub use database::{DuplicateParents, ParseOptions, ParseWarning, Progress};
+pub use diff::DagDiff;
+pub use dominators::Dominators;
+pub use error::{
+    BuildError, CompactDagError, ContractionError, UnknownMetric, WouldCreateCycle, WouldDisconnect,
+};
+#[cfg(feature = "std")]
+pub use error::{DagParseError, DatabaseWriteError};
+pub use hash::FastHasher;
+use hash::{HashMap, NodeMap, NodeSet};
+pub use histogram::{DegreeDistribution, Histogram};
+pub use incremental::IncrementalStats;
+pub use memory::MemoryReport;
+pub use num_bigint::BigUint;
+pub use paths::Diameter;
+use reachability::ReachabilityIndex;
+pub use removal::{RemovalPolicy, RemovalReport};
+pub use report::NodeRow;
+pub use sampling::{Estimate, SampledStatistics};
+pub use schedule::Schedule;
+pub use statistics::{Metric, Spread, Statistics};
+pub use traversal::{Bfs, Dfs, Direction, Generations};
+pub use validate::{HeaderMismatch, ValidationReport};
+pub use view::DagView;
 
 /// This struct holds the node and edges of an Directed Acyclic Graph
+#[derive(Clone)]
 pub struct DirectedAcyclicGraph {
     /// Nodes are stored as a HashSet to achive deduplication
-    nodes: HashSet<usize>,
-    /// Edges are stored as a HashSet to achieve deduplication
-    edges: HashSet<(usize, usize)>,
+    nodes: NodeSet<usize>,
+    /// Forward adjacency, maps a node to the nodes it references
+    neighbors: NodeMap<usize, Vec<usize>>,
+    /// Reverse adjacency, maps a node to the nodes referencing it
+    predecessors: NodeMap<usize, Vec<usize>>,
+    /// Number of edges, kept so counting does not require a traversal
+    edge_count: usize,
+    /// Origin node ids every path ends at, sorted ascending and never empty
+    origins: Vec<usize>,
+    /// Weights of weighted edges, edges missing from the map weigh 1.0
+    weights: NodeMap<(usize, usize), f64>,
+    /// Multiplicities of repeated edges, edges missing from the map occur once
+    multiplicities: NodeMap<(usize, usize), usize>,
+    /// Arbitrary key value metadata attached to nodes
+    attributes: NodeMap<usize, HashMap<String, String>>,
+    /// Transitive closure built on request, dropped whenever the graph changes
+    reachability: Option<ReachabilityIndex>,
+    /// If nodes left without edges are purged after removals
+    purge_isolated: bool,
+}
+
+impl Default for DirectedAcyclicGraph {
+    fn default() -> Self {
+        DirectedAcyclicGraph::new()
+    }
+}
+
+/// Shows the counts and the sorted edges instead of the internal maps
+impl fmt::Debug for DirectedAcyclicGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut edges: Vec<(usize, usize)> = self.edges().collect();
+        edges.sort_unstable();
+
+        f.debug_struct("DirectedAcyclicGraph")
+            .field("origins", &self.origins)
+            .field("node_count", &self.nodes.len())
+            .field("edge_count", &self.edge_count)
+            .field("edges", &edges)
+            .finish()
+    }
+}
+
+/// Prints a compact one line summary, see `statistics` for the full set
+impl fmt::Display for DirectedAcyclicGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DAG with {} nodes, {} edges, {} tips and max depth {}",
+            self.nodes.len(),
+            self.edge_count,
+            self.tip_count(),
+            self.max_depth()
+        )
+    }
+}
+
+/// Two DAGs are equal if they have the same nodes and edges
+/// Origins, weights and attributes are not compared, see `is_isomorphic_to` to ignore node ids
+impl PartialEq for DirectedAcyclicGraph {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+            && self.edge_count == other.edge_count
+            && self
+                .edges()
+                .all(|(from, to)| other.neighbors(from).contains(&to))
+    }
+}
+
+impl Eq for DirectedAcyclicGraph {}
+
+/// Collects `(from, to)` edges into a DAG with node 1 as its origin, see `add_edges`
+impl FromIterator<(usize, usize)> for DirectedAcyclicGraph {
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(edges: I) -> Self {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edges(edges);
+        dag
+    }
+}
+
+/// Inserts `(from, to)` edges, see `add_edges`
+impl Extend<(usize, usize)> for DirectedAcyclicGraph {
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I) {
+        self.add_edges(edges);
+    }
 }
 
 impl DirectedAcyclicGraph {
-    /// Returns an empty Directed Acyclic Graph
+    /// Returns an empty Directed Acyclic Graph with node 1 as its origin
     pub fn new() -> DirectedAcyclicGraph {
-        DirectedAcyclicGraph {
-            nodes: HashSet::new(),
-            edges: HashSet::new(),
-        }
+        DirectedAcyclicGraph::with_origin(1)
     }
 
-    /// Creates a new Directed Acyclic Graph from anything that implements `Read`
-    /// The database structure is as follows
-    /// Line 1: N, the number of nodes in the database
-    /// Lines 2 through N + 1: the node data, where each node consists of the ids of its left and right parents
-    /// Node id 1 is the unique origin of all nodes
-    /// The id of each node in the database is its line number
+    /// Reserves room for at least `nodes` more nodes
     /// # Arguments
-    /// * `reader` - Anything that implements `Read`
-    pub fn from_read(reader: impl Read) -> Result<DirectedAcyclicGraph, std::io::Error> {
-        let mut reader = BufReader::new(reader);
+    /// * `nodes` - Expected number of additional nodes
+    pub fn reserve(&mut self, nodes: usize) {
+        self.nodes.reserve(nodes);
+        self.neighbors.reserve(nodes);
+        self.predecessors.reserve(nodes);
+    }
 
-        let mut line = String::new();
-        reader.read_line(&mut line)?;
+    /// Returns an empty Directed Acyclic Graph with a custom origin
+    /// # Arguments
+    /// * `origin` - Node id all statistics are computed relative to
+    pub fn with_origin(origin: usize) -> DirectedAcyclicGraph {
+        DirectedAcyclicGraph::with_origins(Some(origin))
+    }
 
-        // let size = line.trim().parse::<usize>()?;
+    /// Returns an empty Directed Acyclic Graph with several origins
+    /// Depths are measured to the closest origin
+    /// Panics if `origins` is empty
+    /// # Arguments
+    /// * `origins` - Node ids all statistics are computed relative to
+    pub fn with_origins(origins: impl IntoIterator<Item = usize>) -> DirectedAcyclicGraph {
+        let mut origins: Vec<usize> = origins.into_iter().collect();
+        origins.sort_unstable();
+        origins.dedup();
+        assert!(!origins.is_empty(), "a DAG needs at least one origin");
 
-        let lines: Vec<String> = reader
-            .lines()
-            .filter(|res| res.is_ok())
-            .map(|res| res.unwrap())
-            .collect();
+        DirectedAcyclicGraph {
+            nodes: NodeSet::default(),
+            neighbors: NodeMap::default(),
+            predecessors: NodeMap::default(),
+            edge_count: 0,
+            origins,
+            weights: NodeMap::default(),
+            multiplicities: NodeMap::default(),
+            attributes: NodeMap::default(),
+            reachability: None,
+            purge_isolated: true,
+        }
+    }
 
-        let node_us: Vec<(usize, usize)> = lines
-            .iter()
-            .map(|l| l.trim().split_whitespace().clone())
-            .map(|mut l| (l.next().unwrap(), l.next().unwrap()))
-            .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-            .collect();
+    /// The origin of the DAG, the smallest one if there are several
+    pub fn origin(&self) -> usize {
+        self.origins[0]
+    }
 
-        let mut dag = DirectedAcyclicGraph::new();
-        for (i, (left, right)) in node_us.into_iter().enumerate() {
-            dag.nodes.insert(i + 2);
-            dag.nodes.insert(left);
-            dag.nodes.insert(right);
-            if i + 2 != left {
-                dag.edges.insert((i + 2, left));
-            }
-            if i + 2 != right {
-                dag.edges.insert((i + 2, right));
-            }
-        }
+    /// All origins of the DAG, sorted ascending
+    pub fn origins(&self) -> &[usize] {
+        &self.origins
+    }
 
-        Ok(dag)
+    /// Returns if `node` is one of the origins
+    /// # Arguments
+    /// * `node` - Node id to check
+    pub fn is_origin(&self, node: usize) -> bool {
+        self.origins.binary_search(&node).is_ok()
     }
 
-    /// Get the all possible paths from `node` to node with id 1
+    /// Get the all possible paths from `node` to an origin
+    /// This enumerates every path and is exponential on dense graphs,
+    /// prefer the aggregate statistics unless the paths themselves are needed
+    /// and `paths_to_origin` to stream them
     /// # Arguments
     /// * `node` - Node Id to search
     pub fn depths(&self, node: usize) -> Vec<Vec<usize>> {
-        let neighbors: Vec<usize> = self
-            .edges
-            .iter()
-            .cloned()
-            .filter(|(from, _)| *from == node)
-            .map(|(_, to)| to)
-            .collect();
+        self.paths_to_origin(node).collect()
+    }
 
-        if node == 1 {
-            return vec![vec![1]];
-        }
+    /// Average depth from all nodes to the origin
+    /// The depth of a node is the length of its shortest path to an origin
+    /// Nodes without a path to an origin are skipped, see `unreachable_from_origin`
+    /// Returns `None` if no node has a path to an origin, e.g. for an empty DAG
+    pub fn avg_depth(&self) -> Option<f64> {
+        let depths = self.min_depths();
+        let total: usize = depths.values().sum();
 
-        let mut depths: Vec<Vec<usize>> = Vec::with_capacity(neighbors.len());
-
-        for neighbor in neighbors {
-            if node != neighbor {
-                depths.extend(
-                    self.depths(neighbor)
-                        .into_iter()
-                        .filter(|d| !d.contains(&node))
-                        .map(|mut d| {
-                            d.push(node);
-                            d
-                        }),
-                );
-            }
+        average(total as f64, depths.len())
+    }
+
+    /// Average node count at each depth excluding depth 0
+    /// Returns `None` if there are no paths besides the origins themselves,
+    /// e.g. for an empty or single node DAG
+    pub fn avg_node_per_depth(&self) -> Option<f64> {
+        depth::avg_paths_per_length(&self.paths_per_length())
+    }
+
+    /// Spread of the shortest depths averaged by `avg_depth`
+    /// Returns `None` if no node has a path to an origin
+    pub fn depth_spread(&self) -> Option<Spread> {
+        let mut level_sizes = HashMap::new();
+        for depth in self.min_depths().into_values() {
+            *level_sizes.entry(depth).or_insert(0) += 1;
         }
 
-        return depths;
+        Spread::of_counts(&level_sizes)
     }
 
-    /// Average depth from all nodes to node 1
-    pub fn avg_depth(&self) -> f64 {
-        let mut total = 0.0;
-        let mut depth_count = 0;
-        for node in self.nodes().iter() {
-            if node == &1 {
-                depth_count += 1;
-                continue;
-            }
+    /// Spread of the path counts averaged by `avg_node_per_depth`
+    /// Returns `None` if there are no paths besides the origins themselves
+    pub fn node_per_depth_spread(&self) -> Option<Spread> {
+        Spread::of_path_counts(&self.paths_per_length())
+    }
 
-            let depths = self.depths(*node);
-            total += depths.iter().map(|depth| depth.len() - 1).min().unwrap() as f64;
-            depth_count += 1;
+    /// Spread of the in-references averaged by `avg_ref`
+    /// Returns `None` for an empty DAG
+    pub fn ref_spread(&self) -> Option<Spread> {
+        let mut ref_counts = HashMap::new();
+        for node in self.nodes() {
+            *ref_counts.entry(self.in_references(node)).or_insert(0) += 1;
         }
 
-        total / depth_count as f64
+        Spread::of_counts(&ref_counts)
     }
 
-    /// Average node count at each depth excluding depth 0
-    pub fn avg_node_per_depth(&self) -> f64 {
-        let mut node_count_per_depth = HashMap::new();
+    /// Number of paths to an origin of every length, summed over all non-origin nodes
+    /// Uses `path_length_counts` when the graph is acyclic
+    pub(crate) fn paths_per_length(&self) -> BTreeMap<usize, u128> {
+        if let Some(order) = self.topological_order() {
+            return self.path_length_counts(&order);
+        }
+
+        // Cyclic graphs have no topological order, fall back to enumerating paths
+        let mut node_count_per_depth = BTreeMap::new();
 
         for node in self.nodes() {
-            if node == &1 {
+            if self.is_origin(node) {
                 continue;
             }
-            for depth in self.depths(*node) {
+            for depth in self.depths(node) {
                 let count = node_count_per_depth.entry(depth.len()).or_insert(0);
                 *count += 1;
             }
         }
 
-        node_count_per_depth.values().sum::<i32>() as f64 / node_count_per_depth.len() as f64
+        node_count_per_depth
     }
 
-    /// Average in-reference per node
-    pub fn avg_ref(&self) -> f64 {
-        let mut total = 0;
-        for node in self.nodes() {
-            total += self.edges().iter().filter(|(_, to)| *to == *node).count();
+    /// Average in-reference per node, repeated edges counted by their multiplicity
+    /// Returns `None` for an empty DAG
+    pub fn avg_ref(&self) -> Option<f64> {
+        average(self.reference_count() as f64, self.nodes.len())
+    }
+
+    /// Average out-reference per node, repeated edges counted by their multiplicity
+    /// Returns `None` for an empty DAG
+    pub fn avg_out_ref(&self) -> Option<f64> {
+        average(self.reference_count() as f64, self.nodes.len())
+    }
+
+    /// Smallest out-reference count of any node, see `out_references`
+    /// Returns 0 for an empty DAG
+    pub fn min_out_ref(&self) -> usize {
+        self.nodes()
+            .map(|node| self.out_references(node))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Largest out-reference count of any node, see `out_references`
+    /// Returns 0 for an empty DAG
+    pub fn max_out_ref(&self) -> usize {
+        self.nodes()
+            .map(|node| self.out_references(node))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Share of the possible edges that exist, a DAG of V nodes has at most V * (V - 1) / 2
+    /// Returns `None` for DAGs with fewer than two nodes
+    pub fn density(&self) -> Option<f64> {
+        let nodes = self.nodes.len();
+        if nodes < 2 {
+            return None;
         }
 
-        total as f64 / self.nodes.len() as f64
+        Some(self.edge_count as f64 / (nodes * (nodes - 1) / 2) as f64)
     }
 
-    /// Average out-reference per node
-    pub fn avg_out_ref(&self) -> f64 {
-        let mut total = 0;
-        for node in self.nodes() {
-            total += self.edges().iter().filter(|(from, _)| *from == *node).count();
+    /// Average number of children of the nodes that have any, the nodes referencing them
+    /// Returns `None` if no node is referenced
+    pub fn avg_branching_factor(&self) -> Option<f64> {
+        average(self.edge_count as f64, self.predecessors.len())
+    }
+
+    /// Share of the nodes that are tips, see `tip_count`
+    /// Returns `None` for an empty DAG
+    pub fn leaf_fraction(&self) -> Option<f64> {
+        average(self.tip_count() as f64, self.nodes.len())
+    }
+
+    /// Number of references, the edges counted by their multiplicity
+    /// Equal to `edge_count` unless some edges repeat, see `edge_multiplicity`
+    pub fn reference_count(&self) -> usize {
+        self.edge_count + self.multiplicities.values().map(|m| m - 1).sum::<usize>()
+    }
+
+    /// Number of references made by `node`, its out-degree counted by edge multiplicity
+    /// Returns 0 for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to count out-references of
+    pub fn out_references(&self, node: usize) -> usize {
+        if self.multiplicities.is_empty() {
+            return self.out_degree(node);
         }
 
-        total as f64 / self.nodes.len() as f64
+        self.neighbors(node)
+            .iter()
+            .map(|&to| self.multiplicities.get(&(node, to)).copied().unwrap_or(1))
+            .sum()
+    }
+
+    /// Number of references to `node`, its in-degree counted by edge multiplicity
+    /// Returns 0 for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to count in-references of
+    pub fn in_references(&self, node: usize) -> usize {
+        if self.multiplicities.is_empty() {
+            return self.in_degree(node);
+        }
+
+        self.predecessors(node)
+            .iter()
+            .map(|&from| self.multiplicities.get(&(from, node)).copied().unwrap_or(1))
+            .sum()
+    }
+
+    /// Number of edges starting from `node`, the nodes it references
+    /// Returns 0 for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to count out-references of
+    pub fn out_degree(&self, node: usize) -> usize {
+        self.neighbors(node).len()
+    }
+
+    /// Number of edges ending at `node`, the nodes referencing it
+    /// Returns 0 for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to count in-references of
+    pub fn in_degree(&self, node: usize) -> usize {
+        self.predecessors(node).len()
     }
 
-    /// Longest depth
+    /// Longest depth, counted in nodes along the longest path to an origin
     pub fn max_depth(&self) -> usize {
+        if let Some(order) = self.topological_order() {
+            return self
+                .max_depths(&order)
+                .values()
+                .max()
+                .map_or(0, |depth| depth + 1);
+        }
+
+        // Cyclic graphs have no topological order, fall back to enumerating paths
         let mut max = 0;
         for node in self.nodes() {
-            for depth in self.depths(*node) {
+            for depth in self.depths(node) {
                 if depth.len() > max {
                     max = depth.len();
                 }
@@ -168,14 +457,114 @@ impl DirectedAcyclicGraph {
         max
     }
 
-    /// Borrow nodes of the DAG
-    pub fn nodes(&self) -> &HashSet<usize> {
-        &self.nodes
+    /// Iterate over the nodes of the DAG
+    pub fn nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    /// Iterate over the edges of the DAG as `(from, to)` pairs
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors
+            .iter()
+            .flat_map(|(from, tos)| tos.iter().map(move |to| (*from, *to)))
+    }
+
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns if the node exists in the DAG
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn contains_node(&self, node: usize) -> bool {
+        self.nodes.contains(&node)
+    }
+
+    /// Returns if the edge exists in the DAG
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn contains_edge(&self, from: usize, to: usize) -> bool {
+        self.neighbors(from).contains(&to)
+    }
+
+    /// Nodes referenced by `node` (the destinations of its edges)
+    /// Returns an empty slice for unknown nodes
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        self.neighbors.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Nodes referencing `node` (the sources of edges ending at it)
+    /// Returns an empty slice for unknown nodes
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn predecessors(&self, node: usize) -> &[usize] {
+        self.predecessors.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Attaches a metadata attribute to a node, overwriting any previous value for `key`
+    /// Returns if the attribute got actually stored, which requires the node to exist
+    /// # Arguments
+    /// * `node` - Node id to attach the attribute to
+    /// * `key` - Attribute name
+    /// * `value` - Attribute value
+    pub fn set_node_attr(
+        &mut self,
+        node: usize,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> bool {
+        if !self.nodes.contains(&node) {
+            return false;
+        }
+
+        self.attributes
+            .entry(node)
+            .or_default()
+            .insert(key.into(), value.into());
+        true
+    }
+
+    /// Value of the attribute `key` of `node`
+    /// # Arguments
+    /// * `node` - Node id to look up
+    /// * `key` - Attribute name
+    pub fn node_attr(&self, node: usize, key: &str) -> Option<&str> {
+        self.attributes
+            .get(&node)
+            .and_then(|attributes| attributes.get(key))
+            .map(String::as_str)
     }
 
-    /// Borrow edges of the DAG
-    pub fn edges(&self) -> &HashSet<(usize, usize)> {
-        &self.edges
+    /// All attributes of `node`
+    /// Returns `None` if the node has no attributes
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn node_attrs(&self, node: usize) -> Option<&HashMap<String, String>> {
+        self.attributes.get(&node)
+    }
+
+    /// Removes the attribute `key` from `node`
+    /// Returns the removed value
+    /// # Arguments
+    /// * `node` - Node id to remove the attribute from
+    /// * `key` - Attribute name
+    pub fn remove_node_attr(&mut self, node: usize, key: &str) -> Option<String> {
+        let attributes = self.attributes.get_mut(&node)?;
+        let value = attributes.remove(key);
+        if attributes.is_empty() {
+            self.attributes.remove(&node);
+        }
+
+        value
     }
 
     /// Inserts a new edge to the DAG
@@ -188,83 +577,272 @@ impl DirectedAcyclicGraph {
         if from == to {
             return false;
         }
+        self.reachability = None;
+        self.insert_edge(from, to)
+    }
+
+    /// Inserts many edges at once, dropping derived indexes only once instead of per edge
+    /// Self-referencing edges are skipped
+    /// Returns how many edges got actually added to the DAG
+    /// # Arguments
+    /// * `edges` - `(from, to)` pairs to insert
+    pub fn add_edges(&mut self, edges: impl IntoIterator<Item = (usize, usize)>) -> usize {
+        let edges = edges.into_iter();
+        self.nodes.reserve(edges.size_hint().0);
+        self.reachability = None;
+
+        edges
+            .filter(|(from, to)| from != to && self.insert_edge(*from, *to))
+            .count()
+    }
+
+    /// Inserts an edge and its nodes without touching derived indexes
+    /// Returns if the edge was not present yet
+    /// # Arguments
+    /// * `from` - Start node id, must differ from `to`
+    /// * `to` - Destination node id
+    fn insert_edge(&mut self, from: usize, to: usize) -> bool {
         self.nodes.insert(from);
         self.nodes.insert(to);
 
-        self.edges.insert((from, to))
+        let neighbors = self.neighbors.entry(from).or_default();
+        if neighbors.contains(&to) {
+            return false;
+        }
+        neighbors.push(to);
+        self.predecessors.entry(to).or_default().push(from);
+        self.edge_count += 1;
+
+        true
+    }
+
+    /// Inserts a new weighted edge to the DAG
+    /// Adds the nodes to the DAG if they dont exist
+    /// The weight of an already existing edge is overwritten
+    /// Returns if the edge got actually added to DAG
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    /// * `weight` - Weight of the edge, unweighted edges weigh 1.0
+    pub fn add_weighted_edge(&mut self, from: usize, to: usize, weight: f64) -> bool {
+        let added = self.add_edge(from, to);
+        if from != to {
+            self.weights.insert((from, to), weight);
+        }
+
+        added
+    }
+
+    /// Weight of the edge from `from` to `to`
+    /// Returns `None` if the edge does not exist
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn edge_weight(&self, from: usize, to: usize) -> Option<f64> {
+        if !self.neighbors(from).contains(&to) {
+            return None;
+        }
+
+        Some(self.weights.get(&(from, to)).copied().unwrap_or(1.0))
+    }
+
+    /// Inserts an edge that occurs `multiplicity` times, e.g. a repeated citation
+    /// Adds the nodes to the DAG if they dont exist
+    /// The multiplicity of an already existing edge is overwritten, values below 1 count as 1
+    /// Returns if the edge got actually added to DAG
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    /// * `multiplicity` - How many times the edge occurs
+    pub fn add_edge_with_multiplicity(
+        &mut self,
+        from: usize,
+        to: usize,
+        multiplicity: usize,
+    ) -> bool {
+        let added = self.add_edge(from, to);
+        if from != to {
+            if multiplicity > 1 {
+                self.multiplicities.insert((from, to), multiplicity);
+            } else {
+                self.multiplicities.remove(&(from, to));
+            }
+        }
+
+        added
+    }
+
+    /// How many times the edge from `from` to `to` occurs
+    /// Returns `None` if the edge does not exist
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn edge_multiplicity(&self, from: usize, to: usize) -> Option<usize> {
+        if !self.neighbors(from).contains(&to) {
+            return None;
+        }
+
+        Some(self.multiplicities.get(&(from, to)).copied().unwrap_or(1))
     }
 
+    /// Inserts a new edge to the DAG unless it would introduce a cycle
+    /// Adds the nodes to the DAG if they dont exist
+    /// Returns if the edge got actually added to DAG, or an error if `to` already reaches `from`
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn try_add_edge(&mut self, from: usize, to: usize) -> Result<bool, WouldCreateCycle> {
+        if from == to || self.is_reachable(to, from) {
+            return Err(WouldCreateCycle { from, to });
+        }
+
+        Ok(self.add_edge(from, to))
+    }
+
+    /// Inserts a node without any edges
+    /// Returns if the node got actually added to DAG
+    /// # Arguments
+    /// * `node` - Node id to insert
+    pub fn add_node(&mut self, node: usize) -> bool {
+        self.nodes.insert(node)
+    }
+
+    /// Sets if nodes without any edges are purged by `remove_edge` and `remove_node`, the default
+    /// Only the nodes a removal leaves without edges are purged, nodes inserted by `add_node`
+    /// are kept until `purge_isolated_nodes` is called
+    /// Disable it to represent isolated nodes or to purge once after bulk removals
+    /// # Arguments
+    /// * `purge` - If isolated nodes should be purged
+    pub fn set_purge_isolated_nodes(&mut self, purge: bool) {
+        self.purge_isolated = purge;
+    }
 
     /// Removes the specified edge from the DAF if it exists on the edges
-    /// Also purges any edgeless nodes
-    /// Warning: This method does not currently validates if after removal origin becomes unreachable
-    /// So be careful while removing any edges
+    /// Also purges any edgeless nodes unless disabled by `set_purge_isolated_nodes`
+    /// Warning: This method does not validate if nodes lose their path to the origin,
+    /// use `remove_edge_checked` to refuse such removals
     /// Returns if the edge got actually removed from DAG
     /// # Arguments
     /// * `from` - Starting node id
     /// * `to` - Destination node id
     pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
-        if self.edges.contains(&(from, to)) {
-            self.edges.remove(&(from, to));
-            self.purge_stale_nodes();
-            return true;
+        if !self.contains_edge(from, to) {
+            return false;
         }
 
-        return false;
+        self.reachability = None;
+        self.unlink(from, to);
+        if self.purge_isolated {
+            self.purge_if_isolated(from);
+            self.purge_if_isolated(to);
+        }
+        true
     }
 
     /// Removes the specified node from the DAG alongside with any edges that references that node
+    /// Neighbors left without any edge are removed as well unless disabled by
+    /// `set_purge_isolated_nodes`, see `remove_node_with` for the other policies and a report
     /// Returns if the node got actually removed from the DAG
     /// # Arguments
-    /// * `node` - Node id to remove (can't be 1)
+    /// * `node` - Node id to remove (can't be an origin)
     pub fn remove_node(&mut self, node: usize) -> bool {
-        if node == 1 || !self.nodes.contains(&node) {
-            return false;
-        }
-
-        self.nodes.remove(&node);
-        self.purge_stale_edges();
-        return true;
+        let policy = if self.purge_isolated {
+            RemovalPolicy::Cascade
+        } else {
+            RemovalPolicy::KeepIsolated
+        };
+        self.remove_node_with(node, policy)
+            .is_ok_and(|report| !report.is_empty())
     }
 
-    /// Purges stale nodes (nodes that does not have any edges) from the DAG
-    fn purge_stale_nodes(&mut self) {
-        let mut nodes_to_remove = HashSet::new();
-        for node in self.nodes.iter() {
-            if *node == 1 {
-                continue;
-            }
-
-            if self.edges.iter().filter(|(from, to)| *from == *node || *to == *node).count() == 0 {
-                nodes_to_remove.insert(*node);
-            };
+    /// Removes every node without any edges except the origins, e.g. after bulk removals
+    /// with purging disabled by `set_purge_isolated_nodes`
+    /// Returns the number of removed nodes
+    pub fn purge_isolated_nodes(&mut self) -> usize {
+        let isolated: Vec<usize> = self
+            .nodes()
+            .filter(|&node| self.is_isolated(node))
+            .collect();
+        for node in &isolated {
+            self.nodes.remove(node);
+            self.attributes.remove(node);
         }
 
-        for node in nodes_to_remove {
-            self.remove_node(node);
+        if !isolated.is_empty() {
+            self.reachability = None;
         }
+        isolated.len()
     }
 
-    /// Purges stale edges (edges that reference non existent nodes)
-    fn purge_stale_edges(&mut self) {
-        let mut edges_to_remove = HashSet::new();
-        for (from, to) in self.edges.iter() {
-            if !self.nodes.contains(from) || !self.nodes.contains(to) {
-                edges_to_remove.insert((*from, *to));
-            }
+    /// Removes `node` if it is not an origin and has no edges left
+    /// # Arguments
+    /// * `node` - Node id to check
+    fn purge_if_isolated(&mut self, node: usize) {
+        if self.is_isolated(node) {
+            self.nodes.remove(&node);
+            self.attributes.remove(&node);
         }
+    }
 
-        for (from, to) in edges_to_remove {
-            self.remove_edge(from, to);
-        }
+    /// Returns if `node` is not an origin and has no edges, looking at the adjacency list lengths only
+    /// # Arguments
+    /// * `node` - Node id to check
+    fn is_isolated(&self, node: usize) -> bool {
+        !self.is_origin(node) && self.out_degree(node) == 0 && self.in_degree(node) == 0
+    }
+}
+
+/// Divides `total` by `count`, `None` if there is nothing to average over
+/// # Arguments
+/// * `total` - Sum of the averaged values
+/// * `count` - Number of averaged values
+pub(crate) fn average(total: f64, count: usize) -> Option<f64> {
+    if count == 0 {
+        None
+    } else {
+        Some(total / count as f64)
+    }
+}
+
+/// Removes `value` from the adjacency list of `key`, dropping the list once it is empty
+/// Returns if the value was present
+/// # Arguments
+/// * `adjacency` - Forward or reverse adjacency map
+/// * `key` - Node whose list is modified
+/// * `value` - Node to remove from the list
+fn detach(adjacency: &mut NodeMap<usize, Vec<usize>>, key: usize, value: usize) -> bool {
+    let list = match adjacency.get_mut(&key) {
+        Some(list) => list,
+        None => return false,
+    };
+    let position = match list.iter().position(|v| *v == value) {
+        Some(position) => position,
+        None => return false,
+    };
+
+    list.swap_remove(position);
+    if list.is_empty() {
+        adjacency.remove(&key);
     }
+    true
+}
+
+/// Sample database shared by the unit tests, six nodes with a longest depth of 5
+#[cfg(test)]
+pub(crate) const SAMPLE_DATABASE: &str = "5\n1 1\n1 2\n2 2\n3 6\n3 3\n";
+
+/// Returns the graph described by `SAMPLE_DATABASE`
+#[cfg(test)]
+pub(crate) fn sample_dag() -> DirectedAcyclicGraph {
+    DirectedAcyclicGraph::from_read(SAMPLE_DATABASE.as_bytes()).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::DirectedAcyclicGraph;
+    use crate::{sample_dag, DagParseError, DirectedAcyclicGraph, WouldCreateCycle};
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_if_dag_constructed_correctly() {
         let database = "5
 1 1
@@ -278,17 +856,232 @@ mod tests {
 
         let dag = DirectedAcyclicGraph::from_read(database.as_bytes()).unwrap();
 
-        assert_eq!(dag.nodes.len(), nodes.len());
-        assert_eq!(dag.edges.len(), edges.len());
+        assert_eq!(dag.nodes().count(), nodes.len());
+        assert_eq!(dag.edges().count(), edges.len());
+        assert_eq!(dag.node_count(), nodes.len());
+        assert_eq!(dag.edge_count(), edges.len());
 
         for node in dag.nodes() {
-            assert!(nodes.contains(node));
+            assert!(nodes.contains(&node));
+            assert!(dag.contains_node(node));
         }
 
         for edge in dag.edges() {
             assert!(edges.contains(&edge));
+            assert!(dag.contains_edge(edge.0, edge.1));
         }
+        assert!(!dag.contains_node(7));
+        assert!(!dag.contains_edge(1, 2));
+
+        assert_eq!(dag.neighbors(5), &[3, 6]);
+        assert_eq!(dag.predecessors(3), &[5, 6]);
 
         assert_eq!(dag.max_depth(), 5);
+        assert!((dag.avg_depth().unwrap() - 8.0 / 6.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_out_ref_statistics() {
+        let dag = sample_dag();
+
+        assert_eq!(dag.min_out_ref(), 0);
+        assert_eq!(dag.max_out_ref(), 2);
+        assert!((dag.avg_out_ref().unwrap() - 7.0 / 6.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_edge_multiplicity() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        assert!(dag.add_edge_with_multiplicity(3, 1, 3));
+        dag.add_edge(3, 2);
+
+        assert_eq!(dag.edge_count(), 3);
+        assert_eq!(dag.reference_count(), 5);
+        assert_eq!(dag.in_degree(1), 2);
+        assert_eq!(dag.in_references(1), 4);
+        assert_eq!(dag.out_references(3), 4);
+        assert_eq!(dag.max_out_ref(), 4);
+        assert_eq!(dag.avg_ref(), Some(5.0 / 3.0));
+        assert_eq!(dag.statistics().avg_ref, Some(5.0 / 3.0));
+
+        dag.remove_edge(3, 1);
+        assert_eq!(dag.edge_multiplicity(3, 1), None);
+        assert_eq!(dag.reference_count(), 2);
+    }
+
+    #[test]
+    fn test_add_edges() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        assert!(dag.build_reachability_index());
+
+        let added = dag.add_edges(vec![(2, 1), (3, 1), (3, 2), (4, 4), (4, 3), (3, 2)]);
+        assert_eq!(added, 3);
+        assert_eq!(dag.edges().count(), 4);
+        assert!(!dag.nodes().any(|node| node > 4));
+        assert!(dag.is_reachable(4, 1));
+
+        let mut collected: DirectedAcyclicGraph =
+            vec![(2, 1), (3, 1), (3, 2)].into_iter().collect();
+        collected.extend(vec![(4, 3)]);
+        assert_eq!(collected, dag);
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        let mut left = DirectedAcyclicGraph::new();
+        left.add_edge(2, 1);
+        left.add_edge(3, 2);
+        left.add_edge(3, 1);
+
+        let mut right = DirectedAcyclicGraph::new();
+        right.add_edge(3, 1);
+        right.add_weighted_edge(3, 2, 2.0);
+        right.add_edge(2, 1);
+        assert!(left == right);
+
+        right.remove_edge(3, 1);
+        right.add_edge(3, 4);
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn test_debug_and_display() {
+        let mut dag = DirectedAcyclicGraph::default();
+        dag.add_edge(3, 1);
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 2);
+
+        let copy = dag.clone();
+        dag.remove_edge(3, 1);
+        assert_eq!(copy.edges().count(), 3);
+
+        assert_eq!(
+            format!("{:?}", copy),
+            "DirectedAcyclicGraph { origins: [1], node_count: 3, edge_count: 3, edges: [(2, 1), (3, 1), (3, 2)] }"
+        );
+        assert_eq!(
+            copy.to_string(),
+            "DAG with 3 nodes, 3 edges, 1 tips and max depth 3"
+        );
+    }
+
+    #[test]
+    fn test_isolated_nodes() {
+        let mut dag = DirectedAcyclicGraph::new();
+        assert!(dag.add_node(1));
+        assert!(dag.add_node(5));
+        assert!(!dag.add_node(5));
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 2);
+        assert_eq!(dag.node_count(), 4);
+
+        dag.set_purge_isolated_nodes(false);
+        dag.remove_edge(3, 2);
+        assert!(dag.contains_node(3) && dag.contains_node(5));
+
+        dag.set_purge_isolated_nodes(true);
+        dag.remove_edge(2, 1);
+        assert!(!dag.contains_node(2));
+        assert_eq!(dag.node_count(), 3);
+
+        assert_eq!(dag.purge_isolated_nodes(), 2);
+        assert_eq!(dag.nodes().collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn test_malformed_database_reports_line() {
+        let missing = DirectedAcyclicGraph::from_read("2\n1 1\n1\n".as_bytes());
+        assert!(matches!(
+            missing,
+            Err(DagParseError::MissingField {
+                line: 3,
+                field: "right parent"
+            })
+        ));
+
+        let invalid = DirectedAcyclicGraph::from_read("2\n1 x\n1 1\n".as_bytes());
+        assert!(matches!(
+            invalid,
+            Err(DagParseError::InvalidInteger { line: 2, .. })
+        ));
+
+        let header = DirectedAcyclicGraph::from_read("two\n1 1\n".as_bytes());
+        assert!(matches!(
+            header,
+            Err(DagParseError::BadHeader { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_try_add_edge_refuses_cycles() {
+        let mut dag = DirectedAcyclicGraph::new();
+        assert_eq!(dag.try_add_edge(2, 1), Ok(true));
+        assert_eq!(dag.try_add_edge(3, 2), Ok(true));
+        assert_eq!(dag.try_add_edge(3, 1), Ok(true));
+        assert_eq!(dag.try_add_edge(3, 1), Ok(false));
+
+        assert_eq!(
+            dag.try_add_edge(1, 3),
+            Err(WouldCreateCycle { from: 1, to: 3 })
+        );
+        assert!(dag.try_add_edge(2, 2).is_err());
+        assert!(dag.is_acyclic());
+        assert_eq!(dag.edges().count(), 3);
+    }
+
+    #[test]
+    fn test_custom_origins() {
+        let mut dag = DirectedAcyclicGraph::with_origins(vec![20, 10]);
+        dag.add_edge(11, 10);
+        dag.add_edge(12, 11);
+        dag.add_edge(21, 20);
+        dag.add_edge(12, 21);
+
+        assert_eq!(dag.origin(), 10);
+        assert_eq!(dag.origins(), &[10, 20]);
+        assert_eq!(dag.max_depth(), 3);
+        assert!((dag.avg_depth().unwrap() - 4.0 / 5.0).abs() < f64::EPSILON);
+        assert_eq!(dag.depths(12).len(), 2);
+        assert!(!dag.remove_node(20));
+    }
+
+    #[test]
+    fn test_node_attributes() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 2);
+
+        assert!(dag.set_node_attr(2, "label", "second"));
+        assert!(dag.set_node_attr(2, "timestamp", "1618000000"));
+        assert!(!dag.set_node_attr(9, "label", "missing"));
+        assert_eq!(dag.node_attr(2, "label"), Some("second"));
+        assert_eq!(dag.node_attrs(2).map(|attrs| attrs.len()), Some(2));
+        assert_eq!(
+            dag.remove_node_attr(2, "timestamp"),
+            Some("1618000000".to_string())
+        );
+
+        dag.remove_node(2);
+        assert_eq!(dag.node_attr(2, "label"), None);
+    }
+
+    #[test]
+    fn test_depths_on_deep_chain() {
+        let mut dag = DirectedAcyclicGraph::new();
+        for node in 2..=200_000 {
+            dag.add_edge(node, node - 1);
+        }
+
+        let depths = dag.depths(200_000);
+        assert_eq!(depths.len(), 1);
+        assert_eq!(depths[0].len(), 200_000);
+        assert_eq!(depths[0][0], 1);
+        assert_eq!(depths[0][199_999], 200_000);
+
+        let mut paths = dag.depths(3);
+        paths.sort();
+        assert_eq!(paths, vec![vec![1, 2, 3]]);
     }
 }
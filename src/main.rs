@@ -1,19 +1,1257 @@
-use dag_statistics::DirectedAcyclicGraph;
+use std::collections::HashMap;
+use std::error::Error;
 use std::fs::File;
-use std::env::args;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
-fn main() -> Result<(), Box<dyn std::error::Error + 'static + Send + Sync>> {
-    let args = args();
-    let filename = args.skip(1).next().expect("Expected a filename argument");
-    let file = File::open(&filename)?;
+use clap::{value_parser, Arg, ArgMatches, Args, FromArgMatches, Parser, Subcommand, ValueEnum};
+use dag_statistics::{
+    decompress, DirectedAcyclicGraph, DuplicateParents, Metric, ParseOptions, SampledStatistics,
+};
 
-    let dag = DirectedAcyclicGraph::from_read(file)?;
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
 
-    println!("AVG DAG DEPTH: {:.2}", dag.avg_depth());
-    println!("AVG NODES PER DEPTH: {:.2}", dag.avg_node_per_depth());
-    println!("AVG REF: {:.3}", dag.avg_ref());
-    println!("AVG OUT REF: {:.3}", dag.avg_out_ref());
-    println!("MAX DEPTH: {}", dag.max_depth());
+/// Metrics of a single graph paired with their values
+type Values = Vec<(Metric, Option<f64>)>;
+
+/// Statistics and tooling for directed acyclic graphs
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print every statistic of a graph
+    /// Several files are summarized in a combined table with the average of every metric
+    Stats {
+        /// Graph files or glob patterns to read, `-` or omitted for stdin
+        #[arg(default_value = "-")]
+        files: Vec<String>,
+        #[command(flatten)]
+        options: ReadOptions,
+        /// How the statistics are printed
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Number of files processed in parallel
+        #[arg(long, short, default_value_t = 1)]
+        jobs: usize,
+        /// Comma separated metrics to compute, e.g. `avg-depth,max-depth`, all by default
+        /// On-demand metrics like `pagerank` are only computed when listed
+        #[arg(long, value_delimiter = ',')]
+        metrics: Vec<Metric>,
+        /// Estimate the average depth and references from this many randomly sampled nodes,
+        /// for graphs too large for the exact statistics
+        /// Rejects `--metrics` and assertions on the metrics it does not estimate
+        #[arg(long, value_name = "NODES", conflicts_with = "metrics")]
+        sample: Option<usize>,
+        /// Seed selecting the sampled nodes
+        #[arg(long, requires = "sample", default_value_t = 0)]
+        seed: u64,
+        /// Print the estimated memory used by every graph to stderr
+        #[arg(long)]
+        mem: bool,
+        #[command(flatten)]
+        assertions: Assertions,
+    },
+    /// Check a graph for cycles, unreachable nodes, self-references, duplicate edges and a wrong
+    /// header, exiting with status 1 if any are found
+    Validate {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Export a graph as Graphviz DOT for visualisation
+    #[command(allow_missing_positional = true)]
+    Export {
+        #[command(flatten)]
+        input: Input,
+        /// DOT file to write, `-` for stdout
+        output: PathBuf,
+        /// Place nodes of the same depth on the same rank
+        #[arg(long)]
+        ranked: bool,
+    },
+    /// Convert a graph between formats, e.g. `convert in.db out.dot`
+    /// Formats are inferred from the file extensions unless `--from` or `--to` is given
+    #[command(allow_missing_positional = true)]
+    Convert {
+        #[command(flatten)]
+        input: Input,
+        /// File to write, `-` for stdout
+        output: PathBuf,
+        /// Output format, inferred from the output extension by default
+        #[arg(long, value_enum)]
+        to: Option<Format>,
+    },
+    /// Look up a single node: its depth, degrees, parents, children and ancestors
+    #[command(allow_missing_positional = true)]
+    Query {
+        #[command(flatten)]
+        input: Input,
+        /// Node to look up
+        node: usize,
+        /// Also print the paths from the node to the origin
+        #[arg(long)]
+        paths: bool,
+        /// Stop after this many paths
+        #[arg(long, requires = "paths")]
+        max_paths: Option<usize>,
+    },
+    /// Print the cut nodes, whose removal disconnects others from the origin, by bottleneck score
+    Bottlenecks {
+        #[command(flatten)]
+        input: Input,
+        /// Only print this many nodes
+        #[arg(long)]
+        top: Option<usize>,
+    },
+    /// Print the nodes with the highest betweenness centrality
+    Centrality {
+        #[command(flatten)]
+        input: Input,
+        /// Number of nodes to print
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Print the nodes with the highest value of a per-node metric, ties by node id
+    Top {
+        #[command(flatten)]
+        input: Input,
+        /// Metric to rank the nodes by
+        #[arg(long, value_enum)]
+        by: Ranking,
+        /// Number of nodes to print
+        #[arg(short = 'n', long, default_value_t = 20)]
+        count: usize,
+    },
+    /// Write per-node statistics, one row per node with its depth, degrees, descendant count and
+    /// path count
+    Report {
+        #[command(flatten)]
+        input: Input,
+        /// File to write, stdout by default
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// How the rows are written
+        #[arg(long, value_enum, default_value_t = ReportFormat::Csv)]
+        format: ReportFormat,
+    },
+    /// Print the paths from a node to the origin or another node, one per line
+    /// Paths are enumerated lazily, so `--max-paths` bounds the work on huge graphs
+    Paths {
+        /// Graph file to read, `-` for stdin
+        file: PathBuf,
+        #[command(flatten)]
+        options: ReadOptions,
+        /// Node the paths start at
+        #[arg(id = "start", value_name = "FROM")]
+        from: usize,
+        /// Node the paths end at, any origin by default
+        #[arg(id = "end", value_name = "TO")]
+        to: Option<usize>,
+        /// Stop after this many paths
+        #[arg(long)]
+        max_paths: Option<usize>,
+        /// Only print a path with the fewest edges
+        #[arg(long, conflicts_with = "longest")]
+        shortest: bool,
+        /// Only print a path with the most edges
+        #[arg(long)]
+        longest: bool,
+    },
+    /// Generate a random graph
+    #[cfg(feature = "generator")]
+    Generate {
+        /// Random graph model
+        #[arg(long, value_enum, default_value_t = Model::Uniform)]
+        model: Model,
+        /// Number of nodes including the origin
+        #[arg(long, default_value_t = 1000)]
+        nodes: usize,
+        /// Seed of the random number generator, random by default
+        #[arg(long)]
+        seed: Option<u64>,
+        /// File to write, stdout by default
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Output format, inferred from the output extension by default
+        #[arg(long, value_enum)]
+        to: Option<Format>,
+        /// Out-references per node of the preferential model
+        #[arg(long, default_value_t = 2, help_heading = "Model options")]
+        parents: usize,
+        /// Nodes per layer of the layered model
+        #[arg(long, default_value_t = 10, help_heading = "Model options")]
+        width: usize,
+        /// Chance of every possible edge between neighboring layers of the layered model
+        #[arg(long, default_value_t = 0.3, help_heading = "Model options")]
+        probability: f64,
+        /// Nodes arriving per round of the tangle model
+        #[arg(long, default_value_t = 10, help_heading = "Model options")]
+        rate: usize,
+        /// Bias of the tangle model walks towards heavy nodes
+        #[arg(long, default_value_t = 0.0, help_heading = "Model options")]
+        alpha: f64,
+    },
+}
+
+/// Bounds on the statistics given by `--assert-<metric>-le` and `--assert-<metric>-ge`,
+/// one pair of flags per metric
+#[derive(Default)]
+struct Assertions {
+    bounds: Vec<Bound>,
+}
+
+/// A single bound on a metric
+struct Bound {
+    metric: Metric,
+    /// The metric has to be at most `limit` if set, at least `limit` otherwise
+    upper: bool,
+    limit: f64,
+}
+
+impl Bound {
+    /// Checks the bound, undefined metrics violate every bound
+    /// # Arguments
+    /// * `values` - Metrics of a single graph
+    fn check(&self, values: &Values) -> std::result::Result<(), String> {
+        let value = values
+            .iter()
+            .find(|(metric, _)| *metric == self.metric)
+            .and_then(|(_, value)| *value);
+        let holds = match value {
+            Some(value) if self.upper => value <= self.limit,
+            Some(value) => value >= self.limit,
+            None => false,
+        };
+
+        if holds {
+            return Ok(());
+        }
+        let relation = if self.upper { "at most" } else { "at least" };
+        let value = value.map_or("undefined".to_string(), |value| value.to_string());
+        Err(format!(
+            "{} is {}, expected {} {}",
+            self.metric, value, relation, self.limit
+        ))
+    }
+}
+
+impl Assertions {
+    /// Flag id of a bound
+    fn id(metric: Metric, upper: bool) -> String {
+        let suffix = if upper { "le" } else { "ge" };
+        format!("assert-{}-{}", metric.name().replace('_', "-"), suffix)
+    }
+
+    /// Fails if a bound is on a metric that `--sample` does not estimate
+    fn check_sampled(&self) -> Result<()> {
+        let unsampled = self
+            .bounds
+            .iter()
+            .find(|bound| !SampledStatistics::METRICS.contains(&bound.metric));
+        match unsampled {
+            Some(bound) => {
+                let supported: Vec<String> = SampledStatistics::METRICS
+                    .iter()
+                    .map(|metric| metric.name().replace('_', "-"))
+                    .collect();
+                Err(format!(
+                    "--{} can not be used with --sample, which only estimates {}",
+                    Assertions::id(bound.metric, bound.upper),
+                    supported.join(", ")
+                )
+                .into())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromArgMatches for Assertions {
+    fn from_arg_matches(matches: &ArgMatches) -> std::result::Result<Self, clap::Error> {
+        let mut assertions = Assertions::default();
+        assertions.update_from_arg_matches(matches)?;
+        Ok(assertions)
+    }
+
+    fn update_from_arg_matches(
+        &mut self,
+        matches: &ArgMatches,
+    ) -> std::result::Result<(), clap::Error> {
+        for metric in Metric::ALL.iter().copied() {
+            for upper in [true, false] {
+                if let Some(limit) = matches.get_one::<f64>(&Assertions::id(metric, upper)) {
+                    self.bounds.push(Bound {
+                        metric,
+                        upper,
+                        limit: *limit,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Args for Assertions {
+    fn augment_args(mut command: clap::Command) -> clap::Command {
+        for metric in Metric::ALL.iter().copied() {
+            for upper in [true, false] {
+                let id = Assertions::id(metric, upper);
+                let relation = if upper { "above" } else { "below" };
+                command = command.arg(
+                    Arg::new(id.clone())
+                        .long(id)
+                        .value_name("LIMIT")
+                        .value_parser(value_parser!(f64))
+                        .help(format!(
+                            "Exit with status 1 if {} is {} LIMIT",
+                            metric, relation
+                        ))
+                        .help_heading("Assertions"),
+                );
+            }
+        }
+        command
+    }
+
+    fn augment_args_for_update(command: clap::Command) -> clap::Command {
+        Assertions::augment_args(command)
+    }
+}
+
+/// Graph file shared by the commands reading a graph
+#[derive(Args)]
+struct Input {
+    /// Graph file to read, `-` or omitted for stdin
+    #[arg(default_value = "-")]
+    file: PathBuf,
+    #[command(flatten)]
+    options: ReadOptions,
+}
+
+/// How the commands reading a graph parse it
+#[derive(Args, Clone)]
+struct ReadOptions {
+    /// Input format, inferred from the file extension by default
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+    /// Accept any number of parents per database node line instead of exactly two
+    #[arg(long)]
+    relaxed: bool,
+    /// Fail on database problems that are otherwise only warned about, like a wrong header
+    #[arg(long)]
+    strict: bool,
+    /// What to do with a parent listed twice on a database node line
+    #[arg(long, value_enum, default_value_t = Duplicates::Collapse)]
+    duplicate_parents: Duplicates,
+}
+
+impl ReadOptions {
+    /// Parsing options of the database format
+    fn database(&self) -> ParseOptions {
+        ParseOptions::new()
+            .relaxed(self.relaxed)
+            .strict(self.strict)
+            .duplicate_parents(match self.duplicate_parents {
+                Duplicates::Reject => DuplicateParents::Reject,
+                Duplicates::Collapse => DuplicateParents::Collapse,
+                Duplicates::Count => DuplicateParents::Count,
+            })
+    }
+}
+
+/// Duplicate parent policies of the database format
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Duplicates {
+    /// Fail on the line listing a parent twice
+    Reject,
+    /// Keep a single edge
+    Collapse,
+    /// Keep a single edge and count its multiplicity
+    Count,
+}
+
+/// Random graph models of the `generate` command
+#[cfg(feature = "generator")]
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Model {
+    /// Every node references two uniformly chosen earlier nodes
+    Uniform,
+    /// Every node references `--parents` earlier nodes with preferential attachment
+    Preferential,
+    /// Layers of `--width` nodes referencing the previous layer
+    Layered,
+    /// Tangle grown by weighted random walk tip selection
+    Tangle,
+}
+
+/// Output formats of the `stats` command
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human readable `LABEL: value` lines
+    Table,
+    /// Single JSON object keyed by metric name
+    Json,
+    /// Header row of metric names followed by a row of values
+    Csv,
+}
+
+/// Per-node metrics of the `top` command
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Ranking {
+    /// Nodes referencing the node, counted by edge multiplicity
+    InDegree,
+    /// Nodes the node references, counted by edge multiplicity
+    OutDegree,
+    /// Longest path to an origin
+    Depth,
+    /// Nodes referencing the node directly or transitively
+    CumulativeWeight,
+}
+
+/// Output formats of the `report` command
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    /// Header row of column names followed by a row per node
+    Csv,
+    /// One JSON object per node and line
+    Ndjson,
+}
+
+/// Graph file formats
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Native database format
+    Database,
+    /// Graphviz DOT
+    Dot,
+    /// Comma separated edge list
+    Csv,
+    /// Tab separated edge list
+    Tsv,
+    /// Node-link JSON
+    Json,
+    /// GraphML
+    Graphml,
+}
+
+impl Format {
+    /// Guesses the format from a file extension, falling back to the database format
+    /// A trailing compression extension is skipped
+    /// # Arguments
+    /// * `path` - File path to inspect
+    fn infer(path: &Path) -> Format {
+        // Compressed files are named after the format inside, e.g. `graph.csv.gz`
+        let compressed = matches!(
+            path.extension().and_then(|extension| extension.to_str()),
+            Some("gz") | Some("zst") | Some("zstd")
+        );
+        let path = if compressed {
+            Path::new(path.file_stem().unwrap_or_default())
+        } else {
+            path
+        };
+
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("dot") | Some("gv") => Format::Dot,
+            Some("csv") => Format::Csv,
+            Some("tsv") => Format::Tsv,
+            Some("json") => Format::Json,
+            Some("graphml") | Some("xml") => Format::Graphml,
+            _ => Format::Database,
+        }
+    }
+
+    /// Error for formats whose cargo feature is disabled
+    #[allow(dead_code)]
+    fn unsupported(self) -> Box<dyn Error + Send + Sync> {
+        let feature = match self {
+            Format::Json => "json",
+            Format::Graphml => "graphml",
+            _ => unreachable!("only optional formats can be unsupported"),
+        };
+        format!(
+            "the {0} format requires building with the `{0}` feature",
+            feature
+        )
+        .into()
+    }
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("error: {}", err);
+        process::exit(2);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    match cli.command {
+        Command::Stats {
+            files,
+            options,
+            format,
+            jobs,
+            mut metrics,
+            sample,
+            seed,
+            mem,
+            assertions,
+        } => {
+            // Asserted metrics are computed even if `--metrics` leaves them out
+            if !metrics.is_empty() {
+                for bound in &assertions.bounds {
+                    if !metrics.contains(&bound.metric) {
+                        metrics.push(bound.metric);
+                    }
+                }
+            }
+
+            if sample.is_some() {
+                assertions.check_sampled()?;
+            }
+
+            let inputs = expand(&files, &options)?;
+            let names = inputs.iter().map(|input| input.file.display().to_string());
+            let rows: Vec<(String, Values)> = match sample {
+                // Estimates are printed with their confidence intervals as a table,
+                // other formats get the point estimates
+                Some(sample_size) => {
+                    let mut rows = Vec::new();
+                    for (input, name) in inputs.iter().zip(names) {
+                        let dag = input.load()?;
+                        if mem {
+                            print_memory(&input.file, &dag);
+                        }
+                        let sampled = dag.statistics_sampled(sample_size, seed);
+                        if format == OutputFormat::Table {
+                            if inputs.len() > 1 {
+                                println!("{}:", name);
+                            }
+                            println!("{}", sampled);
+                        }
+                        rows.push((name, sampled.values()));
+                    }
+                    rows
+                }
+                None => names
+                    .zip(values_of(&inputs, &metrics, jobs, mem)?)
+                    .collect(),
+            };
+            if sample.is_none() || format != OutputFormat::Table {
+                if let [(_, values)] = rows.as_slice() {
+                    print_values(values, format);
+                } else {
+                    print_combined(&rows, format);
+                }
+            }
+
+            let mut failed = false;
+            for (file, values) in &rows {
+                for bound in &assertions.bounds {
+                    if let Err(message) = bound.check(values) {
+                        eprintln!("assertion failed: {}: {}", file, message);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        Command::Validate { input } => {
+            // Text formats are checked on the raw input, catching what the parsers silently accept
+            let report = match input.format() {
+                Format::Database => DirectedAcyclicGraph::validate_database(
+                    input.open()?,
+                    &input.options.database(),
+                )?,
+                Format::Csv => DirectedAcyclicGraph::validate_edge_list(input.open()?, ',')?,
+                Format::Tsv => DirectedAcyclicGraph::validate_edge_list(input.open()?, '\t')?,
+                _ => input.load()?.validate(),
+            };
+            println!("{}", report);
+            if !report.is_valid() {
+                process::exit(1);
+            }
+        }
+        Command::Export {
+            input,
+            output,
+            ranked,
+        } => {
+            let dag = input.load()?;
+            let mut writer = create(Some(&output))?;
+            if ranked {
+                dag.to_dot_ranked(&mut writer)?;
+            } else {
+                dag.to_dot(&mut writer)?;
+            }
+            writer.flush()?;
+        }
+        Command::Convert { input, output, to } => {
+            let dag = input.load()?;
+            let format = to.unwrap_or_else(|| Format::infer(&output));
+            save(&dag, Some(&output), format)?;
+        }
+        Command::Bottlenecks { input, top } => {
+            let dag = input.load()?;
+            let cyclic = || "the graph is cyclic".to_string();
+            let scores = dag.bottleneck_scores().ok_or_else(cyclic)?;
+            let cut = dag.cut_nodes().ok_or_else(cyclic)?;
+            print_ranking(cut.into_iter().map(|node| (node, scores[&node])), top);
+        }
+        Command::Centrality { input, top } => {
+            let dag = input.load()?;
+            print_ranking(dag.top_k_central(top).into_iter(), None);
+        }
+        Command::Top { input, by, count } => {
+            let dag = input.load()?;
+            let cyclic = || "the graph is cyclic".to_string();
+            let values: HashMap<usize, usize> = match by {
+                Ranking::InDegree => dag
+                    .nodes()
+                    .map(|node| (node, dag.in_references(node)))
+                    .collect(),
+                Ranking::OutDegree => dag
+                    .nodes()
+                    .map(|node| (node, dag.out_references(node)))
+                    .collect(),
+                Ranking::Depth => dag.longest_depths().ok_or_else(cyclic)?,
+                Ranking::CumulativeWeight => dag.cumulative_weights().ok_or_else(cyclic)?,
+            };
+
+            let mut values: Vec<(usize, usize)> = values.into_iter().collect();
+            values.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            for (node, value) in values.into_iter().take(count) {
+                println!("{}: {}", node, value);
+            }
+        }
+        Command::Report {
+            input,
+            output,
+            format,
+        } => {
+            let dag = input.load()?;
+            let mut writer = create(output.as_deref())?;
+            write_report(&dag, &mut writer, format)?;
+            writer.flush()?;
+        }
+        Command::Paths {
+            file,
+            options,
+            from,
+            to,
+            max_paths,
+            shortest,
+            longest,
+        } => {
+            let dag = Input { file, options }.load()?;
+            check_node(&dag, from)?;
+            if let Some(to) = to {
+                check_node(&dag, to)?;
+            }
+
+            let targets: Vec<usize> = match to {
+                Some(to) => vec![to],
+                None => dag.origins().to_vec(),
+            };
+            if shortest {
+                let path = targets
+                    .iter()
+                    .filter_map(|target| dag.shortest_path(from, *target))
+                    .min_by_key(Vec::len);
+                print_paths(path.into_iter(), None)?;
+            } else if longest {
+                let path = targets
+                    .iter()
+                    .filter_map(|target| dag.longest_path(from, *target))
+                    .max_by_key(Vec::len);
+                print_paths(path.into_iter(), None)?;
+            } else if let Some(to) = to {
+                print_paths(dag.paths_between(from, to), max_paths)?;
+            } else {
+                print_paths(dag.paths_to_origin(from).map(reversed), max_paths)?;
+            }
+        }
+        Command::Query {
+            input,
+            node,
+            paths,
+            max_paths,
+        } => {
+            let dag = input.load()?;
+            check_node(&dag, node)?;
+
+            let depth = |depth: Option<usize>| depth.map_or("-".to_string(), |d| d.to_string());
+            let sorted = |nodes: &[usize]| {
+                let mut nodes = nodes.to_vec();
+                nodes.sort_unstable();
+                nodes
+            };
+            println!("NODE: {}", node);
+            println!("MIN DEPTH: {}", depth(dag.min_depth_of(node)));
+            println!("MAX DEPTH: {}", depth(dag.depth_of(node)));
+            println!("IN DEGREE: {}", dag.in_degree(node));
+            println!("OUT DEGREE: {}", dag.out_degree(node));
+            println!("PARENTS: {:?}", sorted(dag.neighbors(node)));
+            println!("CHILDREN: {:?}", sorted(dag.predecessors(node)));
+            println!("ANCESTORS: {}", dag.ancestors(node).len());
+            println!("DESCENDANTS: {}", dag.descendants(node).len());
+            match dag.path_count(node) {
+                Some(count) => println!("PATHS: {}", count),
+                None => println!("PATHS: -"),
+            }
+            println!(
+                "BOTTLENECK SCORE: {}",
+                format_value(dag.bottleneck_score(node), "-")
+            );
+
+            if paths {
+                println!();
+                print_paths(dag.paths_to_origin(node).map(reversed), max_paths)?;
+            }
+        }
+        #[cfg(feature = "generator")]
+        Command::Generate {
+            model,
+            nodes,
+            seed,
+            output,
+            to,
+            parents,
+            width,
+            probability,
+            rate,
+            alpha,
+        } => {
+            use dag_statistics::generator;
+            use rand::rngs::StdRng;
+            use rand::SeedableRng;
+
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(format!("probability {} is not between 0 and 1", probability).into());
+            }
+
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            let dag = match model {
+                Model::Uniform => generator::uniform(nodes, &mut rng),
+                Model::Preferential => generator::preferential_attachment(nodes, parents, &mut rng),
+                Model::Layered => {
+                    let depth = nodes.saturating_sub(1) / width.max(1);
+                    generator::layered(width, depth, probability, &mut rng)
+                }
+                Model::Tangle => generator::tangle(nodes, rate.max(1), alpha, &mut rng),
+            };
+
+            let format =
+                to.unwrap_or_else(|| output.as_deref().map_or(Format::Database, Format::infer));
+            save(&dag, output.as_deref(), format)?;
+        }
+    }
 
     Ok(())
 }
+
+impl Input {
+    /// Format given by `--from` or inferred from the file extension
+    fn format(&self) -> Format {
+        self.options
+            .from
+            .unwrap_or_else(|| Format::infer(&self.file))
+    }
+
+    /// Opens the file, or stdin for `-`, decompressing gzip and zstd content
+    fn open(&self) -> Result<Box<dyn Read>> {
+        if self.file == Path::new("-") {
+            return Ok(decompress(io::stdin())?);
+        }
+
+        let file = File::open(&self.file)
+            .map_err(|err| format!("cannot open {}: {}", self.file.display(), err))?;
+        Ok(decompress(file)?)
+    }
+
+    /// Reads the graph in the given or inferred format
+    /// Stdin is read in the database format unless `--from` says otherwise
+    fn load(&self) -> Result<DirectedAcyclicGraph> {
+        let file = self.open()?;
+        let dag = match self.format() {
+            Format::Database => {
+                let (dag, warnings) =
+                    DirectedAcyclicGraph::from_read_with(file, &self.options.database())?;
+                for warning in warnings {
+                    eprintln!("warning: {}: {}", self.file.display(), warning);
+                }
+                dag
+            }
+            Format::Dot => DirectedAcyclicGraph::from_dot(file)?,
+            Format::Csv => DirectedAcyclicGraph::from_edge_list(file, ',')?,
+            Format::Tsv => DirectedAcyclicGraph::from_edge_list(file, '\t')?,
+            #[cfg(feature = "json")]
+            Format::Json => DirectedAcyclicGraph::from_json(file)?,
+            #[cfg(feature = "graphml")]
+            Format::Graphml => DirectedAcyclicGraph::from_graphml(file)?,
+            #[allow(unreachable_patterns)]
+            format => return Err(format.unsupported()),
+        };
+
+        Ok(dag)
+    }
+}
+
+/// Expands glob patterns into the files they match, other arguments are kept as they are
+/// # Arguments
+/// * `patterns` - File names and glob patterns
+/// * `options` - Read options shared by every file
+fn expand(patterns: &[String], options: &ReadOptions) -> Result<Vec<Input>> {
+    let mut inputs = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            inputs.push(Input {
+                file: PathBuf::from(pattern),
+                options: options.clone(),
+            });
+            continue;
+        }
+
+        let matched = inputs.len();
+        for file in glob::glob(pattern)? {
+            inputs.push(Input {
+                file: file?,
+                options: options.clone(),
+            });
+        }
+        if inputs.len() == matched {
+            return Err(format!("no file matches {}", pattern).into());
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Computes the metrics of every input, in input order
+/// # Arguments
+/// * `inputs` - Graph files to read
+/// * `metrics` - Metrics to compute, every statistic if empty
+/// * `jobs` - Number of worker threads
+/// * `mem` - Print the memory used by every graph
+fn values_of(inputs: &[Input], metrics: &[Metric], jobs: usize, mem: bool) -> Result<Vec<Values>> {
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        let mut results = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let input = match inputs.get(index) {
+                Some(input) => input,
+                None => return results,
+            };
+            let values = input
+                .load()
+                .map(|dag| {
+                    if mem {
+                        print_memory(&input.file, &dag);
+                    }
+                    if metrics.is_empty() {
+                        dag.statistics().values()
+                    } else {
+                        dag.metrics(metrics)
+                    }
+                })
+                .map_err(|err| format!("{}: {}", input.file.display(), err));
+            results.push((index, values));
+        }
+    };
+
+    let mut results = thread::scope(|scope| {
+        let workers: Vec<_> = (1..jobs.clamp(1, inputs.len().max(1)))
+            .map(|_| scope.spawn(worker))
+            .collect();
+        let mut results = worker();
+        for handle in workers {
+            results.extend(handle.join().expect("worker thread panicked"));
+        }
+        results
+    });
+
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, values)| values.map_err(Into::into))
+        .collect()
+}
+
+/// Prints one row per file followed by the average of every metric over the files
+/// # Arguments
+/// * `rows` - File name and metrics of every file
+/// * `format` - Output format
+fn print_combined(rows: &[(String, Values)], format: OutputFormat) {
+    let values: Vec<&Values> = rows.iter().map(|(_, values)| values).collect();
+    let names: Vec<&str> = values
+        .first()
+        .map(|values| values.iter().map(|(metric, _)| metric.name()).collect())
+        .unwrap_or_default();
+    let averages: Vec<Option<f64>> = (0..names.len())
+        .map(|column| {
+            let defined: Vec<f64> = values.iter().filter_map(|row| row[column].1).collect();
+            if defined.is_empty() {
+                None
+            } else {
+                Some(defined.iter().sum::<f64>() / defined.len() as f64)
+            }
+        })
+        .collect();
+
+    let mut table: Vec<(&str, Vec<Option<f64>>)> = rows
+        .iter()
+        .zip(&values)
+        .map(|((file, _), values)| (file.as_str(), values.iter().map(|(_, v)| *v).collect()))
+        .collect();
+
+    match format {
+        OutputFormat::Table => {
+            table.push(("AVERAGE", averages));
+            let mut cells: Vec<Vec<String>> = vec![std::iter::once("FILE")
+                .chain(names.iter().copied())
+                .map(str::to_ascii_uppercase)
+                .collect()];
+            for (file, row) in &table {
+                let row = row.iter().map(|v| format_value(*v, "-"));
+                cells.push(std::iter::once(file.to_string()).chain(row).collect());
+            }
+
+            let widths: Vec<usize> = (0..cells[0].len())
+                .map(|column| cells.iter().map(|row| row[column].len()).max().unwrap_or(0))
+                .collect();
+            for row in cells {
+                let line: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<1$}", cell, width))
+                    .collect();
+                println!("{}", line.join("  ").trim_end());
+            }
+        }
+        OutputFormat::Json => {
+            let object = |row: &[Option<f64>]| {
+                let fields: Vec<String> = names
+                    .iter()
+                    .zip(row)
+                    .map(|(name, v)| format!("\"{}\":{}", name, format_value(*v, "null")))
+                    .collect();
+                fields.join(",")
+            };
+            let files: Vec<String> = table
+                .iter()
+                .map(|(file, row)| format!("{{\"file\":{},{}}}", json_string(file), object(row)))
+                .collect();
+            println!(
+                "{{\"files\":[{}],\"average\":{{{}}}}}",
+                files.join(","),
+                object(&averages)
+            );
+        }
+        OutputFormat::Csv => {
+            table.push(("average", averages));
+            println!("file,{}", names.join(","));
+            for (file, row) in &table {
+                let row: Vec<String> = row.iter().map(|v| format_value(*v, "")).collect();
+                println!("{},{}", file, row.join(","));
+            }
+        }
+    }
+}
+
+/// Quotes `value` as a JSON string, escaping control characters as `\uXXXX`
+/// # Arguments
+/// * `value` - Text to quote
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Formats a single metric, integers are printed without decimals
+/// # Arguments
+/// * `value` - Metric value
+/// * `undefined` - Text printed for undefined values
+fn format_value(value: Option<f64>, undefined: &str) -> String {
+    match value {
+        Some(value) if value.fract() == 0.0 => value.to_string(),
+        Some(value) => format!("{:.3}", value),
+        None => undefined.to_string(),
+    }
+}
+
+/// Fails if the node is not part of the graph
+/// # Arguments
+/// * `dag` - Graph to look in
+/// * `node` - Node id given on the command line
+fn check_node(dag: &DirectedAcyclicGraph, node: usize) -> Result<()> {
+    if dag.contains_node(node) {
+        Ok(())
+    } else {
+        Err(format!("node {} does not exist", node).into())
+    }
+}
+
+/// Prints `node: score` lines, highest score first and ties by node id
+/// # Arguments
+/// * `scores` - Node scores to rank
+/// * `top` - Only print this many nodes
+fn print_ranking(scores: impl Iterator<Item = (usize, f64)>, top: Option<usize>) {
+    let mut scores: Vec<(usize, f64)> = scores.collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    for (node, score) in scores.into_iter().take(top.unwrap_or(usize::MAX)) {
+        println!("{}: {:.3}", node, score);
+    }
+}
+
+/// Prints the memory report of a graph to stderr, each line prefixed by the file name
+/// # Arguments
+/// * `file` - File the graph was read from
+/// * `dag` - Graph to measure
+fn print_memory(file: &Path, dag: &DirectedAcyclicGraph) {
+    let report = dag.memory_usage().to_string();
+    // Locked once, so the lines of parallel workers do not interleave
+    let stderr = io::stderr();
+    let mut stderr = stderr.lock();
+    for line in report.lines() {
+        let _ = writeln!(stderr, "{}: {}", file.display(), line);
+    }
+}
+
+/// Prints paths one per line as space separated node ids
+/// # Arguments
+/// * `paths` - Paths to print, consumed lazily
+/// * `max_paths` - Stop after this many paths
+fn print_paths(paths: impl Iterator<Item = Vec<usize>>, max_paths: Option<usize>) -> Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    for path in paths.take(max_paths.unwrap_or(usize::MAX)) {
+        let path: Vec<String> = path.iter().map(usize::to_string).collect();
+        writeln!(writer, "{}", path.join(" "))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reverses a path so it starts at the node it was enumerated from
+fn reversed(mut path: Vec<usize>) -> Vec<usize> {
+    path.reverse();
+    path
+}
+
+/// Prints the metrics of a single file to stdout
+/// # Arguments
+/// * `values` - Metrics to print
+/// * `format` - Output format
+fn print_values(values: &[(Metric, Option<f64>)], format: OutputFormat) {
+    let value = |value: Option<f64>, undefined: &str| match value {
+        Some(value) => value.to_string(),
+        None => undefined.to_string(),
+    };
+
+    match format {
+        OutputFormat::Table => {
+            for (metric, v) in values {
+                println!("{}: {}", metric.label(), metric.format(*v));
+            }
+        }
+        OutputFormat::Json => {
+            let fields: Vec<String> = values
+                .iter()
+                .map(|(metric, v)| format!("\"{}\":{}", metric, value(*v, "null")))
+                .collect();
+            println!("{{{}}}", fields.join(","));
+        }
+        OutputFormat::Csv => {
+            let names: Vec<&str> = values.iter().map(|(metric, _)| metric.name()).collect();
+            let row: Vec<String> = values.iter().map(|(_, v)| value(*v, "")).collect();
+            println!("{}", names.join(","));
+            println!("{}", row.join(","));
+        }
+    }
+}
+
+/// Writes the `node_report` of a graph, undefined values are left empty or written as `null`
+/// # Arguments
+/// * `dag` - Graph to report on
+/// * `writer` - Destination of the rows
+/// * `format` - Output format
+fn write_report(
+    dag: &DirectedAcyclicGraph,
+    writer: &mut dyn Write,
+    format: ReportFormat,
+) -> Result<()> {
+    let optional =
+        |value: Option<String>, undefined: &str| value.unwrap_or_else(|| undefined.to_string());
+
+    if format == ReportFormat::Csv {
+        writeln!(
+            writer,
+            "id,depth,in_references,out_references,descendant_count,path_count"
+        )?;
+    }
+    for row in dag.node_report() {
+        let depth = row.depth.map(|depth| depth.to_string());
+        let path_count = row.path_count.map(|count| count.to_string());
+        match format {
+            ReportFormat::Csv => writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                row.id,
+                optional(depth, ""),
+                row.in_references,
+                row.out_references,
+                row.descendant_count,
+                optional(path_count, "")
+            )?,
+            ReportFormat::Ndjson => writeln!(
+                writer,
+                "{{\"id\":{},\"depth\":{},\"in_references\":{},\"out_references\":{},\"descendant_count\":{},\"path_count\":{}}}",
+                row.id,
+                optional(depth, "null"),
+                row.in_references,
+                row.out_references,
+                row.descendant_count,
+                optional(path_count, "null")
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Writes the graph in the given format
+/// # Arguments
+/// * `dag` - Graph to write
+/// * `path` - File to write, `None` or `-` for stdout
+/// * `format` - Output format
+fn save(dag: &DirectedAcyclicGraph, path: Option<&Path>, format: Format) -> Result<()> {
+    // Serialized in memory first, so a graph the format can not represent
+    // fails before the output file is created or truncated
+    let mut buffer = Vec::new();
+    match format {
+        Format::Database => dag.to_database(&mut buffer)?,
+        Format::Dot => dag.to_dot(&mut buffer)?,
+        Format::Csv => dag.to_edge_list(&mut buffer, ',')?,
+        Format::Tsv => dag.to_edge_list(&mut buffer, '\t')?,
+        #[cfg(feature = "json")]
+        Format::Json => dag.to_json(&mut buffer)?,
+        #[cfg(feature = "graphml")]
+        Format::Graphml => dag.to_graphml(&mut buffer)?,
+        #[allow(unreachable_patterns)]
+        format => return Err(format.unsupported()),
+    }
+
+    let mut writer = create(path)?;
+    writer.write_all(&buffer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Opens a buffered writer
+/// # Arguments
+/// * `path` - File to create, `None` or `-` for stdout
+fn create(path: Option<&Path>) -> Result<Box<dyn Write>> {
+    match path {
+        Some(path) if path != Path::new("-") => {
+            let file = File::create(path)
+                .map_err(|err| format!("cannot create {}: {}", path.display(), err))?;
+            Ok(Box::new(BufWriter::new(file)))
+        }
+        _ => Ok(Box::new(BufWriter::new(io::stdout()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::{json_string, Assertions, Cli, Format};
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_sampled_assertions() {
+        let parse = |args: &[&str]| {
+            let matches = Cli::command().try_get_matches_from(args).unwrap();
+            let (_, stats) = matches.subcommand().unwrap();
+            Assertions::from_arg_matches(stats).unwrap()
+        };
+
+        let sampled = parse(&[
+            "dag",
+            "stats",
+            "--sample",
+            "10",
+            "--assert-avg-depth-le",
+            "3",
+        ]);
+        assert!(sampled.check_sampled().is_ok());
+        let exact = parse(&[
+            "dag",
+            "stats",
+            "--sample",
+            "10",
+            "--assert-max-depth-le",
+            "3",
+        ]);
+        let message = exact.check_sampled().unwrap_err().to_string();
+        assert!(message.starts_with("--assert-max-depth-le can not be used with --sample"));
+        assert!(Cli::command()
+            .try_get_matches_from(["dag", "stats", "--sample", "10", "--metrics", "avg-depth"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a.db"), "\"a.db\"");
+        assert_eq!(
+            json_string("q\"\\\n\u{7f}\u{1}é"),
+            "\"q\\\"\\\\\\n\\u007f\\u0001é\""
+        );
+        let parsed: String = serde_json::from_str(&json_string("tab\there\u{7f}")).unwrap();
+        assert_eq!(parsed, "tab\there\u{7f}");
+    }
+
+    #[test]
+    fn test_format_inference() {
+        assert!(Format::infer(Path::new("in.db")) == Format::Database);
+        assert!(Format::infer(Path::new("out.DOT")) == Format::Dot);
+        assert!(Format::infer(Path::new("graph.graphml")) == Format::Graphml);
+        assert!(Format::infer(Path::new("-")) == Format::Database);
+        assert!(Format::infer(Path::new("graph.csv.gz")) == Format::Csv);
+    }
+}
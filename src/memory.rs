@@ -0,0 +1,152 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+use core::mem::size_of;
+
+use crate::hash::{HashMap, HashSet};
+use crate::DirectedAcyclicGraph;
+
+/// Estimated heap bytes of a graph by purpose, computed by `DirectedAcyclicGraph::memory_usage`
+/// and `CompactDag::memory_usage`
+/// Estimates are based on the allocated capacities, allocator overhead is not included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryReport {
+    /// Node set and origins
+    pub nodes: usize,
+    /// Forward and reverse adjacency
+    pub edges: usize,
+    /// Edge weights, multiplicities and node attributes
+    pub metadata: usize,
+    /// Optional indexes, like the reachability index
+    pub indexes: usize,
+}
+
+impl MemoryReport {
+    /// Sum of all parts
+    pub fn total(&self) -> usize {
+        self.nodes + self.edges + self.metadata + self.indexes
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "NODES MEMORY: {}", format_bytes(self.nodes))?;
+        writeln!(f, "EDGES MEMORY: {}", format_bytes(self.edges))?;
+        writeln!(f, "METADATA MEMORY: {}", format_bytes(self.metadata))?;
+        writeln!(f, "INDEXES MEMORY: {}", format_bytes(self.indexes))?;
+        write!(f, "TOTAL MEMORY: {}", format_bytes(self.total()))
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Estimates the heap memory held by the graph, to predict the footprint of larger inputs
+    /// Runs in O(V) as the adjacency lists are visited for their capacities
+    pub fn memory_usage(&self) -> MemoryReport {
+        let attributes: usize = self
+            .attributes
+            .values()
+            .map(|attributes| {
+                map_bytes(attributes)
+                    + attributes
+                        .iter()
+                        .map(|(key, value)| key.capacity() + value.capacity())
+                        .sum::<usize>()
+            })
+            .sum();
+
+        MemoryReport {
+            nodes: set_bytes(&self.nodes) + vec_bytes(&self.origins),
+            edges: adjacency_bytes(&self.neighbors) + adjacency_bytes(&self.predecessors),
+            metadata: map_bytes(&self.weights)
+                + map_bytes(&self.multiplicities)
+                + map_bytes(&self.attributes)
+                + attributes,
+            indexes: self
+                .reachability
+                .as_ref()
+                .map_or(0, |index| index.heap_bytes()),
+        }
+    }
+}
+
+/// Heap bytes of the buffer of a vector
+pub(crate) fn vec_bytes<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
+
+/// Heap bytes of the table of a hash map, not including memory owned by the entries
+pub(crate) fn map_bytes<K, V, S>(map: &HashMap<K, V, S>) -> usize {
+    table_bytes::<(K, V)>(map.capacity())
+}
+
+/// Heap bytes of the table of a hash set
+fn set_bytes<T, S>(set: &HashSet<T, S>) -> usize {
+    table_bytes::<T>(set.capacity())
+}
+
+/// Heap bytes of an adjacency map including its lists
+fn adjacency_bytes<S>(adjacency: &HashMap<usize, Vec<usize>, S>) -> usize {
+    map_bytes(adjacency) + adjacency.values().map(vec_bytes).sum::<usize>()
+}
+
+/// Bytes of a SwissTable holding `capacity` entries of `T`, which keeps a control byte
+/// per bucket and a power of two number of buckets at most 7/8 full
+/// # Arguments
+/// * `capacity` - Capacity reported by the map or set
+fn table_bytes<T>(capacity: usize) -> usize {
+    if capacity == 0 {
+        return 0;
+    }
+    let buckets = if capacity < 8 {
+        (capacity + 1).next_power_of_two()
+    } else {
+        (capacity * 8 / 7).next_power_of_two()
+    };
+    buckets * (size_of::<T>() + 1)
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.50 MiB`
+/// # Arguments
+/// * `bytes` - Number of bytes
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_memory_usage() {
+        let mut dag = DirectedAcyclicGraph::new();
+        assert_eq!(dag.memory_usage().edges, 0);
+
+        for node in 2..=1000 {
+            dag.add_edge(node, node / 2);
+        }
+        let report = dag.memory_usage();
+        assert!(report.nodes >= 1000 * 8);
+        assert!(report.edges >= 2 * 999 * 8);
+        assert_eq!(report.indexes, 0);
+        assert_eq!(
+            report.total(),
+            report.nodes + report.edges + report.metadata
+        );
+
+        dag.build_reachability_index();
+        assert!(dag.memory_usage().indexes >= 1000 * 1000 / 8);
+        let compact = dag.to_compact().unwrap().memory_usage();
+        assert!(compact.total() * 2 < report.total());
+        assert!(compact.to_string().ends_with("KiB"));
+    }
+}
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::{detach, DirectedAcyclicGraph};
+
+impl DirectedAcyclicGraph {
+    /// Adds every node, edge and origin of `other` to this DAG, e.g. to stitch partial databases
+    /// Existing weights, multiplicities and attributes win over the ones of `other`
+    /// Edges of `other` can close cycles, check `is_acyclic` when the inputs are not trusted
+    /// # Arguments
+    /// * `other` - DAG to merge in
+    pub fn merge(&mut self, other: &DirectedAcyclicGraph) {
+        self.reachability = None;
+        self.nodes.extend(other.nodes());
+        for (from, to) in other.edges() {
+            self.insert_edge(from, to);
+        }
+        for (edge, weight) in &other.weights {
+            self.weights.entry(*edge).or_insert(*weight);
+        }
+        for (edge, multiplicity) in &other.multiplicities {
+            self.multiplicities.entry(*edge).or_insert(*multiplicity);
+        }
+        for (node, attributes) in &other.attributes {
+            let merged = self.attributes.entry(*node).or_default();
+            for (key, value) in attributes {
+                merged.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+
+        self.origins.extend(other.origins());
+        self.origins.sort_unstable();
+        self.origins.dedup();
+    }
+
+    /// Returns a new DAG holding the nodes, edges and origins of both DAGs, see `merge`
+    /// # Arguments
+    /// * `other` - DAG to combine with
+    pub fn union(&self, other: &DirectedAcyclicGraph) -> DirectedAcyclicGraph {
+        let mut union = DirectedAcyclicGraph::with_origins(self.origins.iter().copied());
+        union.merge(self);
+        union.merge(other);
+        union
+    }
+
+    /// Returns a new DAG holding the nodes and edges present in both DAGs
+    /// Weights, multiplicities and attributes are taken from this DAG
+    /// The common origins stay origins, if there are none the roots of the result are used
+    /// # Arguments
+    /// * `other` - DAG to intersect with
+    pub fn intersection(&self, other: &DirectedAcyclicGraph) -> DirectedAcyclicGraph {
+        let nodes = self
+            .nodes()
+            .filter(|node| other.nodes.contains(node))
+            .collect();
+        let mut intersection = self.subgraph(&nodes);
+
+        let missing: Vec<(usize, usize)> = intersection
+            .edges()
+            .filter(|(from, to)| !other.neighbors(*from).contains(to))
+            .collect();
+        for (from, to) in missing {
+            detach(&mut intersection.neighbors, from, to);
+            detach(&mut intersection.predecessors, to, from);
+            intersection.weights.remove(&(from, to));
+            intersection.multiplicities.remove(&(from, to));
+            intersection.edge_count -= 1;
+        }
+
+        let common: Vec<usize> = self
+            .origins()
+            .iter()
+            .copied()
+            .filter(|origin| other.is_origin(*origin))
+            .collect();
+        if !common.is_empty() {
+            intersection.origins = common;
+        } else {
+            let mut roots: Vec<usize> = intersection.roots().collect();
+            roots.sort_unstable();
+            if !roots.is_empty() {
+                intersection.origins = roots;
+            }
+        }
+
+        intersection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_merge_union_and_intersection() {
+        let mut left = DirectedAcyclicGraph::new();
+        left.add_edge(2, 1);
+        left.add_weighted_edge(3, 2, 2.0);
+        left.add_edge(3, 1);
+
+        let mut right = DirectedAcyclicGraph::new();
+        right.add_weighted_edge(3, 2, 5.0);
+        right.add_edge(3, 1);
+        right.add_edge(4, 3);
+        right.set_node_attr(4, "label", "tip");
+
+        let union = left.union(&right);
+        let mut edges: Vec<(usize, usize)> = union.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, [(2, 1), (3, 1), (3, 2), (4, 3)]);
+        assert_eq!(union.edge_weight(3, 2), Some(2.0));
+        assert_eq!(union.node_attr(4, "label"), Some("tip"));
+
+        let intersection = left.intersection(&right);
+        let mut edges: Vec<(usize, usize)> = intersection.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, [(3, 1), (3, 2)]);
+        assert_eq!(intersection.origins(), &[1]);
+
+        left.merge(&right);
+        assert_eq!(left.edges().count(), 4);
+        assert_eq!(left.max_depth(), 4);
+    }
+}
@@ -0,0 +1,453 @@
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
+use num_bigint::BigUint;
+
+use crate::hash::hash_map::Entry;
+use crate::hash::{HashMap, HashSet};
+use crate::DirectedAcyclicGraph;
+
+/// Longest paths of a DAG, computed by `DirectedAcyclicGraph::diameter`
+/// Paths are ordered along the edges, so the first node is the one further from the origins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diameter {
+    /// Longest of all shortest paths between two nodes, the classic graph diameter
+    pub shortest: Vec<usize>,
+    /// Longest path overall
+    pub longest: Vec<usize>,
+}
+
+/// Lazy depth first enumeration of the paths from a node to an origin or a target node
+struct Paths<'a> {
+    dag: &'a DirectedAcyclicGraph,
+    /// Node the paths end at, any origin if `None`
+    target: Option<usize>,
+    /// Nodes that can reach the target, other branches are pruned
+    can_reach: Option<HashSet<usize>>,
+    /// Current path, starting at the node the enumeration started from
+    path: Vec<usize>,
+    /// Nodes of the current path, a path never visits a node twice
+    on_path: HashSet<usize>,
+    /// Index of the next neighbor to try for each node of the current path
+    next_neighbor: Vec<usize>,
+}
+
+impl Paths<'_> {
+    /// Whether the paths end at `node`
+    fn is_target(&self, node: usize) -> bool {
+        match self.target {
+            Some(target) => node == target,
+            None => self.dag.is_origin(node),
+        }
+    }
+}
+
+impl Iterator for Paths<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        // The current path is walked with an explicit stack instead of recursion,
+        // each frame holds the index of the next neighbor to try for that path node
+        while let Some(&index) = self.next_neighbor.last() {
+            let current = self.path[self.path.len() - 1];
+            let neighbor = if self.is_target(current) {
+                None
+            } else {
+                self.dag.neighbors(current).get(index).copied()
+            };
+
+            match neighbor {
+                Some(neighbor) => {
+                    *self.next_neighbor.last_mut().unwrap() += 1;
+                    let pruned = self
+                        .can_reach
+                        .as_ref()
+                        .is_some_and(|can_reach| !can_reach.contains(&neighbor));
+                    if !pruned && self.on_path.insert(neighbor) {
+                        self.path.push(neighbor);
+                        self.next_neighbor.push(0);
+                    }
+                }
+                None => {
+                    // Paths to an origin are reported origin first, paths to a target start first
+                    let found = if !self.is_target(current) {
+                        None
+                    } else if self.target.is_none() {
+                        Some(self.path.iter().rev().copied().collect())
+                    } else {
+                        Some(self.path.clone())
+                    };
+                    self.on_path.remove(&current);
+                    self.path.pop();
+                    self.next_neighbor.pop();
+                    if found.is_some() {
+                        return found;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Lazily iterate over all possible paths from `node` to an origin, ordered origin first
+    /// Only the current path is kept in memory, so the first few paths can be taken
+    /// even when the total number of paths is astronomical
+    /// # Arguments
+    /// * `node` - Node Id to search
+    pub fn paths_to_origin(&self, node: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        Paths {
+            dag: self,
+            target: None,
+            can_reach: None,
+            path: vec![node],
+            on_path: core::iter::once(node).collect(),
+            next_neighbor: vec![0],
+        }
+    }
+
+    /// Lazily iterate over all paths from `from` to `to` following edges, ordered `from` first
+    /// Branches that can not reach `to` are pruned, so only the current path
+    /// and the set of nodes reaching `to` are kept in memory
+    /// # Arguments
+    /// * `from` - Node id the paths start at
+    /// * `to` - Node id the paths end at
+    pub fn paths_between(&self, from: usize, to: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        let mut can_reach = self.descendants(to);
+        can_reach.insert(to);
+        let exists = self.nodes.contains(&from) && can_reach.contains(&from);
+
+        Paths {
+            dag: self,
+            target: Some(to),
+            can_reach: Some(can_reach),
+            path: vec![from],
+            on_path: core::iter::once(from).collect(),
+            next_neighbor: if exists { vec![0] } else { Vec::new() },
+        }
+    }
+
+    /// Number of distinct paths from `node` to an origin, the same as `depths(node).len()`
+    /// without enumerating the paths, counts grow exponentially so they are unbounded integers
+    /// Returns `None` if a cycle is reachable from `node`
+    /// # Arguments
+    /// * `node` - Node id to count the paths of
+    pub fn path_count(&self, node: usize) -> Option<BigUint> {
+        self.count_paths(node, |current| self.is_origin(current))
+    }
+
+    /// Number of distinct paths from `from` to `to` following edges
+    /// A node has exactly one path to itself
+    /// Returns `None` if a cycle is reachable from `from`
+    /// # Arguments
+    /// * `from` - Node id the paths start at
+    /// * `to` - Node id the paths end at
+    pub fn path_count_between(&self, from: usize, to: usize) -> Option<BigUint> {
+        self.count_paths(from, |current| current == to)
+    }
+
+    /// Path with the fewest edges from `from` to `to`, ordered from `from` to `to`
+    /// Returns `None` if either node is missing or `to` can not be reached
+    /// # Arguments
+    /// * `from` - Node id the path starts at
+    /// * `to` - Node id the path ends at
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if !self.nodes.contains(&from) || !self.nodes.contains(&to) {
+            return None;
+        }
+
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+                while let Some(node) = previous.get(&path[path.len() - 1]) {
+                    path.push(*node);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &neighbor in self.neighbors(node) {
+                if neighbor != from && !previous.contains_key(&neighbor) {
+                    previous.insert(neighbor, node);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Path with the most edges from `from` to `to`, ordered from `from` to `to`
+    /// Returns `None` if either node is missing, `to` can not be reached
+    /// or a cycle is reachable from `from`, which makes the longest path unbounded
+    /// # Arguments
+    /// * `from` - Node id the path starts at
+    /// * `to` - Node id the path ends at
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if !self.nodes.contains(&from) || !self.nodes.contains(&to) {
+            return None;
+        }
+
+        // Post-order DFS, each finished node maps to its longest distance to `to`
+        // and the neighbor that path continues with
+        let mut longest: HashMap<usize, Option<(usize, Option<usize>)>> = HashMap::new();
+        let mut on_path = HashSet::new();
+        let mut stack = vec![(from, 0)];
+        on_path.insert(from);
+        while let Some((current, index)) = stack.last_mut() {
+            let current = *current;
+            let neighbor = if current == to {
+                None
+            } else {
+                self.neighbors(current).get(*index).copied()
+            };
+
+            match neighbor {
+                Some(neighbor) => {
+                    *index += 1;
+                    if on_path.contains(&neighbor) {
+                        return None;
+                    }
+                    if !longest.contains_key(&neighbor) {
+                        on_path.insert(neighbor);
+                        stack.push((neighbor, 0));
+                    }
+                }
+                None => {
+                    let best = if current == to {
+                        Some((0, None))
+                    } else {
+                        self.neighbors(current)
+                            .iter()
+                            .filter_map(|neighbor| {
+                                longest[neighbor].map(|(length, _)| (length, *neighbor))
+                            })
+                            .max()
+                            .map(|(length, neighbor)| (length + 1, Some(neighbor)))
+                    };
+                    longest.insert(current, best);
+                    on_path.remove(&current);
+                    stack.pop();
+                }
+            }
+        }
+
+        longest[&from]?;
+        let mut path = vec![from];
+        while let Some((_, Some(next))) = longest[&path[path.len() - 1]] {
+            path.push(next);
+        }
+        Some(path)
+    }
+
+    /// Longest shortest path and longest path between any two nodes, including their endpoints
+    /// Finding the longest shortest path takes a breadth first search from every node
+    /// Ties are broken towards the smallest node ids
+    /// Returns `None` if the DAG is empty or cyclic
+    pub fn diameter(&self) -> Option<Diameter> {
+        let order = self.topological_order()?;
+        let mut nodes = order.clone();
+        nodes.sort_unstable();
+
+        let mut shortest = (0, *nodes.first()?, *nodes.first()?);
+        for &from in &nodes {
+            let mut distances = HashMap::new();
+            let mut queue = VecDeque::new();
+            distances.insert(from, 0);
+            queue.push_back(from);
+            while let Some(node) = queue.pop_front() {
+                let distance = distances[&node];
+                if distance > shortest.0
+                    || (distance == shortest.0 && (from, node) < (shortest.1, shortest.2))
+                {
+                    shortest = (distance, from, node);
+                }
+                for &neighbor in self.neighbors(node) {
+                    if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                        entry.insert(distance + 1);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        // Longest path starting at every node and the neighbor it continues with,
+        // filled in reverse topological order so the neighbors are always known
+        let mut longest: HashMap<usize, (usize, Option<usize>)> = HashMap::new();
+        for &node in order.iter().rev() {
+            let best = self
+                .neighbors(node)
+                .iter()
+                .map(|neighbor| (longest[neighbor].0 + 1, *neighbor))
+                .max_by_key(|(length, neighbor)| (*length, core::cmp::Reverse(*neighbor)))
+                .map_or((0, None), |(length, neighbor)| (length, Some(neighbor)));
+            longest.insert(node, best);
+        }
+        let start = nodes
+            .iter()
+            .copied()
+            .max_by_key(|node| (longest[node].0, core::cmp::Reverse(*node)))?;
+        let mut path = vec![start];
+        while let (_, Some(next)) = longest[&path[path.len() - 1]] {
+            path.push(next);
+        }
+
+        Some(Diameter {
+            shortest: self.shortest_path(shortest.1, shortest.2)?,
+            longest: path,
+        })
+    }
+
+    /// Counts the paths from `start` to the nodes matching `is_target` with a post-order DFS
+    /// Paths end at the first target they reach
+    /// # Arguments
+    /// * `start` - Node id the paths start at
+    /// * `is_target` - Returns if a node ends a path
+    fn count_paths(&self, start: usize, is_target: impl Fn(usize) -> bool) -> Option<BigUint> {
+        if !self.nodes.contains(&start) {
+            return Some(BigUint::default());
+        }
+
+        let mut counts: HashMap<usize, BigUint> = HashMap::new();
+        let mut on_path = HashSet::new();
+        let mut stack = vec![(start, 0)];
+        on_path.insert(start);
+        while let Some((current, index)) = stack.last_mut() {
+            let current = *current;
+            let neighbor = if is_target(current) {
+                None
+            } else {
+                self.neighbors(current).get(*index).copied()
+            };
+
+            match neighbor {
+                Some(neighbor) => {
+                    *index += 1;
+                    if on_path.contains(&neighbor) {
+                        return None;
+                    }
+                    if !counts.contains_key(&neighbor) {
+                        on_path.insert(neighbor);
+                        stack.push((neighbor, 0));
+                    }
+                }
+                None => {
+                    let count = if is_target(current) {
+                        BigUint::from(1u8)
+                    } else {
+                        self.neighbors(current)
+                            .iter()
+                            .map(|neighbor| &counts[neighbor])
+                            .sum()
+                    };
+                    counts.insert(current, count);
+                    on_path.remove(&current);
+                    stack.pop();
+                }
+            }
+        }
+
+        counts.remove(&start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::HashSet;
+    use crate::{sample_dag, BigUint, Diameter, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_path_count() {
+        let dag = sample_dag();
+        for node in dag.nodes() {
+            assert_eq!(
+                dag.path_count(node),
+                Some(BigUint::from(dag.depths(node).len()))
+            );
+        }
+        assert_eq!(dag.path_count_between(5, 3), Some(BigUint::from(2u8)));
+        assert_eq!(dag.path_count_between(3, 5), Some(BigUint::from(0u8)));
+        assert_eq!(dag.path_count_between(4, 4), Some(BigUint::from(1u8)));
+
+        let between: Vec<Vec<usize>> = dag.paths_between(5, 3).collect();
+        assert_eq!(between.len(), 2);
+        assert!(between
+            .iter()
+            .all(|path| path[0] == 5 && path[path.len() - 1] == 3));
+        assert_eq!(dag.paths_between(3, 5).count(), 0);
+        assert_eq!(dag.paths_between(4, 4).collect::<Vec<_>>(), [[4]]);
+
+        // Every node references the previous two, so path counts follow the Fibonacci numbers
+        let mut ladder = DirectedAcyclicGraph::new();
+        ladder.add_edge(2, 1);
+        for node in 3..=200 {
+            ladder.add_edge(node, node - 1);
+            ladder.add_edge(node, node - 2);
+        }
+        let fibonacci = "280571172992510140037611932413038677189525";
+        assert_eq!(ladder.path_count(200).unwrap().to_string(), fibonacci);
+
+        let first: Vec<Vec<usize>> = ladder.paths_to_origin(200).take(3).collect();
+        assert_eq!(first.len(), 3);
+        assert!(first
+            .iter()
+            .all(|path| path[0] == 1 && path[path.len() - 1] == 200));
+    }
+
+    #[test]
+    fn test_shortest_and_longest_path() {
+        let mut dag = sample_dag();
+        assert_eq!(dag.shortest_path(4, 4), Some(vec![4]));
+        assert_eq!(dag.longest_path(4, 4), Some(vec![4]));
+        assert_eq!(dag.shortest_path(1, 5), None);
+        assert_eq!(dag.longest_path(1, 5), None);
+        assert_eq!(dag.shortest_path(5, 99), None);
+        assert_eq!(dag.longest_path(99, 1), None);
+
+        assert_eq!(dag.shortest_path(5, 1), Some(vec![5, 3, 1]));
+        assert_eq!(dag.longest_path(5, 1), Some(vec![5, 6, 3, 2, 1]));
+
+        // The cycle 2 -> 3 -> 2 makes every path from 5 through 3 unbounded
+        dag.add_edge(2, 3);
+        assert_eq!(dag.shortest_path(5, 1), Some(vec![5, 3, 1]));
+        assert_eq!(dag.longest_path(5, 1), None);
+        assert_eq!(dag.longest_path(4, 4), Some(vec![4]));
+    }
+
+    #[test]
+    fn test_diameter() {
+        let dag = sample_dag();
+        let diameter = dag.diameter().unwrap();
+        assert_eq!(
+            diameter,
+            Diameter {
+                shortest: vec![4, 2, 1],
+                longest: vec![5, 6, 3, 2, 1],
+            }
+        );
+        assert_eq!(diameter.longest.len(), dag.max_depth());
+
+        // 4 reaches 1 through either 3 or 2 and 6 through 5, all three paths have 3 nodes
+        let mut tied = DirectedAcyclicGraph::new();
+        for (from, to) in [(6, 5), (5, 1), (4, 3), (4, 2), (3, 1), (2, 1)] {
+            tied.add_edge(from, to);
+        }
+        let diameter = tied.diameter().unwrap();
+        assert_eq!(diameter.longest, [4, 2, 1]);
+        assert_eq!(diameter.shortest.len(), 3);
+        assert_eq!(diameter.shortest[0], 4);
+        assert_eq!(diameter.shortest[2], 1);
+
+        let empty = dag.subgraph(&HashSet::new());
+        assert_eq!(empty.node_count(), 0);
+        assert_eq!(empty.diameter(), None);
+
+        let mut cyclic = sample_dag();
+        cyclic.add_edge(2, 3);
+        assert_eq!(cyclic.diameter(), None);
+    }
+}
@@ -0,0 +1,243 @@
+use alloc::{vec, vec::Vec};
+
+use crate::hash::{HashMap, HashSet};
+use crate::memory::{map_bytes, vec_bytes};
+use crate::{DirectedAcyclicGraph, Direction};
+
+/// Transitive closure of a DAG stored as one ancestor bitset per node
+#[derive(Clone)]
+pub(crate) struct ReachabilityIndex {
+    /// Maps node ids to their bit position
+    positions: HashMap<usize, usize>,
+    /// Bitset of the ancestors of every node, indexed by bit position
+    ancestors: Vec<Vec<u64>>,
+}
+
+impl ReachabilityIndex {
+    /// Heap bytes of the positions and bitsets
+    pub(crate) fn heap_bytes(&self) -> usize {
+        map_bytes(&self.positions)
+            + vec_bytes(&self.ancestors)
+            + self.ancestors.iter().map(vec_bytes).sum::<usize>()
+    }
+
+    /// Returns if `to` is an ancestor of `from`, `None` if either node is not indexed
+    fn contains(&self, from: usize, to: usize) -> Option<bool> {
+        let from = *self.positions.get(&from)?;
+        let to = *self.positions.get(&to)?;
+        Some(self.ancestors[from][to / 64] & (1 << (to % 64)) != 0)
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Returns if `to` can be reached from `from` by following edges
+    /// A node always reaches itself
+    /// Answered in constant time once `build_reachability_index` was called, otherwise with a fresh search
+    /// # Arguments
+    /// * `from` - Node id to start from
+    /// * `to` - Node id to search for
+    pub fn is_reachable(&self, from: usize, to: usize) -> bool {
+        if from == to {
+            return self.nodes.contains(&from);
+        }
+        if let Some(reachable) = self
+            .reachability
+            .as_ref()
+            .and_then(|index| index.contains(from, to))
+        {
+            return reachable;
+        }
+        // Nothing references `to`, which is always the case for freshly appended nodes
+        if self.predecessors(to).is_empty() {
+            return false;
+        }
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            for &neighbor in self.neighbors(node) {
+                if neighbor == to {
+                    return true;
+                }
+                if visited.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Materializes the transitive closure as bitsets so `is_reachable` becomes a constant time lookup
+    /// Uses one bit per node pair, e.g. 125 MB for 100,000 nodes
+    /// The index is dropped again by any mutation of the graph
+    /// Returns if the index got built, which requires the graph to be acyclic
+    pub fn build_reachability_index(&mut self) -> bool {
+        let order = match self.topological_order() {
+            Some(order) => order,
+            None => return false,
+        };
+
+        let positions: HashMap<usize, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(position, node)| (*node, position))
+            .collect();
+        let words = order.len().div_ceil(64);
+
+        let mut ancestors = vec![Vec::new(); order.len()];
+        for (position, node) in order.iter().enumerate().rev() {
+            let mut bits = vec![0u64; words];
+            for neighbor in self.neighbors(*node) {
+                let neighbor = positions[neighbor];
+                bits[neighbor / 64] |= 1 << (neighbor % 64);
+                for (word, neighbor_word) in bits.iter_mut().zip(&ancestors[neighbor]) {
+                    *word |= neighbor_word;
+                }
+            }
+            ancestors[position] = bits;
+        }
+
+        self.reachability = Some(ReachabilityIndex {
+            positions,
+            ancestors,
+        });
+        true
+    }
+
+    /// All nodes reachable from `node` by following edges, i.e. its parents and their parents
+    /// The node itself is not included
+    /// # Arguments
+    /// * `node` - Node id to start from
+    pub fn ancestors(&self, node: usize) -> HashSet<usize> {
+        self.bfs(node, Direction::Forward).skip(1).collect()
+    }
+
+    /// Number of ancestors of `node`
+    /// # Arguments
+    /// * `node` - Node id to start from
+    pub fn ancestor_count(&self, node: usize) -> usize {
+        self.ancestors(node).len()
+    }
+
+    /// All nodes referencing `node` directly or transitively
+    /// The node itself is not included
+    /// # Arguments
+    /// * `node` - Node id to start from
+    pub fn descendants(&self, node: usize) -> HashSet<usize> {
+        self.bfs(node, Direction::Reverse).skip(1).collect()
+    }
+
+    /// Number of descendants of `node`
+    /// # Arguments
+    /// * `node` - Node id to start from
+    pub fn descendant_count(&self, node: usize) -> usize {
+        self.descendants(node).len()
+    }
+
+    /// Lowest common ancestors of `a` and `b`, like a merge base in a commit graph
+    /// These are the common ancestors not referenced by any other common ancestor,
+    /// a node counts as its own ancestor here so `a` is returned if `b` references it
+    /// Empty if either node is missing or they share no ancestor
+    /// # Arguments
+    /// * `a` - First node id
+    /// * `b` - Second node id
+    pub fn lowest_common_ancestors(&self, a: usize, b: usize) -> HashSet<usize> {
+        if !self.nodes.contains(&a) || !self.nodes.contains(&b) {
+            return HashSet::new();
+        }
+
+        let mut of_a = self.ancestors(a);
+        of_a.insert(a);
+        let mut of_b = self.ancestors(b);
+        of_b.insert(b);
+        let common: HashSet<usize> = of_a.intersection(&of_b).copied().collect();
+
+        // Common ancestors are closed under taking ancestors, so a common ancestor
+        // is lowest exactly when none of its direct referencers is common as well
+        common
+            .iter()
+            .copied()
+            .filter(|node| {
+                !self
+                    .predecessors(*node)
+                    .iter()
+                    .any(|predecessor| common.contains(predecessor))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::HashSet;
+
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let dag = sample_dag();
+
+        assert_eq!(
+            dag.ancestors(6),
+            [1, 2, 3].iter().copied().collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            dag.descendants(3),
+            [5, 6].iter().copied().collect::<HashSet<_>>()
+        );
+        assert_eq!(dag.descendant_count(1), 5);
+        assert_eq!(dag.ancestor_count(1), 0);
+        assert_eq!(dag.ancestor_count(42), 0);
+    }
+
+    #[test]
+    fn test_lowest_common_ancestors() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 1);
+        dag.add_edge(4, 2);
+        dag.add_edge(4, 3);
+        dag.add_edge(5, 2);
+        dag.add_edge(5, 3);
+        dag.add_edge(6, 5);
+
+        let set = |nodes: &[usize]| nodes.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(dag.lowest_common_ancestors(4, 6), set(&[2, 3]));
+        assert_eq!(dag.lowest_common_ancestors(6, 5), set(&[5]));
+        assert_eq!(dag.lowest_common_ancestors(2, 3), set(&[1]));
+        assert_eq!(dag.lowest_common_ancestors(2, 42), set(&[]));
+    }
+
+    #[test]
+    fn test_reachability_index_matches_search() {
+        let mut dag = DirectedAcyclicGraph::new();
+        for node in 2..200 {
+            dag.add_edge(node, node / 2);
+            if node % 7 == 0 {
+                dag.add_edge(node, node - 3);
+            }
+        }
+
+        let mut expected = Vec::new();
+        for from in 1..200 {
+            for to in 1..200 {
+                expected.push(dag.is_reachable(from, to));
+            }
+        }
+
+        assert!(dag.build_reachability_index());
+        let mut indexed = Vec::new();
+        for from in 1..200 {
+            for to in 1..200 {
+                indexed.push(dag.is_reachable(from, to));
+            }
+        }
+        assert_eq!(indexed, expected);
+        assert!(dag.is_reachable(8, 1));
+        assert!(!dag.is_reachable(1, 8));
+
+        dag.add_edge(1, 200);
+        assert!(dag.is_reachable(1, 200));
+    }
+}
@@ -0,0 +1,220 @@
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
+use crate::hash::HashSet;
+use crate::{detach, DirectedAcyclicGraph, WouldDisconnect};
+
+/// What `remove_node_with` does with the rest of the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalPolicy {
+    /// Also removes the neighbors left without any edge, like `remove_node` does by default
+    Cascade,
+    /// Removes only the node and its edges, neighbors left without edges stay isolated
+    KeepIsolated,
+    /// Refuses the removal if another node would lose its last path to an origin
+    RejectIfDisconnects,
+}
+
+/// Everything removed by `remove_node_with`, sorted ascending
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemovalReport {
+    /// Removed nodes, the requested one and any cascaded ones
+    pub nodes: Vec<usize>,
+    /// Removed `(from, to)` edges
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl RemovalReport {
+    /// Returns if nothing got removed
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Removes `node` and its edges, handling the rest of the graph according to `policy`
+    /// Origins and missing nodes are never removed and yield an empty report
+    /// Returns everything that got removed, or the nodes that would be disconnected
+    /// from the origins if the policy rejects the removal
+    /// # Arguments
+    /// * `node` - Node id to remove
+    /// * `policy` - How far the removal may spread
+    pub fn remove_node_with(
+        &mut self,
+        node: usize,
+        policy: RemovalPolicy,
+    ) -> Result<RemovalReport, WouldDisconnect> {
+        let mut report = RemovalReport::default();
+        if self.is_origin(node) || !self.nodes.contains(&node) {
+            return Ok(report);
+        }
+
+        if policy == RemovalPolicy::RejectIfDisconnects {
+            let nodes = self.disconnected_by(|from, to| from != node && to != node);
+            let nodes: Vec<usize> = nodes.into_iter().filter(|&n| n != node).collect();
+            if !nodes.is_empty() {
+                return Err(WouldDisconnect { nodes });
+            }
+        }
+
+        self.reachability = None;
+        let mut pending = vec![node];
+        while let Some(node) = pending.pop() {
+            if !self.nodes.remove(&node) {
+                continue;
+            }
+            self.attributes.remove(&node);
+            report.nodes.push(node);
+
+            let mut edges: Vec<(usize, usize)> =
+                self.neighbors(node).iter().map(|&to| (node, to)).collect();
+            edges.extend(self.predecessors(node).iter().map(|&from| (from, node)));
+            for (from, to) in edges {
+                self.unlink(from, to);
+                report.edges.push((from, to));
+
+                let other = if from == node { to } else { from };
+                if policy == RemovalPolicy::Cascade && self.is_isolated(other) {
+                    pending.push(other);
+                }
+            }
+        }
+
+        report.nodes.sort_unstable();
+        report.edges.sort_unstable();
+        Ok(report)
+    }
+
+    /// Removes the edge from `from` to `to` unless a node would lose its last path to an origin
+    /// Purges edgeless nodes like `remove_edge`
+    /// Returns if the edge got actually removed, or the nodes the removal would disconnect
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub fn remove_edge_checked(&mut self, from: usize, to: usize) -> Result<bool, WouldDisconnect> {
+        if !self.contains_edge(from, to) {
+            return Ok(false);
+        }
+
+        let nodes = self.disconnected_by(|f, t| (f, t) != (from, to));
+        if !nodes.is_empty() {
+            return Err(WouldDisconnect { nodes });
+        }
+
+        Ok(self.remove_edge(from, to))
+    }
+
+    /// Removes an existing edge and its weight and multiplicity without touching derived indexes
+    /// # Arguments
+    /// * `from` - Start node id
+    /// * `to` - Destination node id
+    pub(crate) fn unlink(&mut self, from: usize, to: usize) {
+        if detach(&mut self.neighbors, from, to) {
+            detach(&mut self.predecessors, to, from);
+            self.weights.remove(&(from, to));
+            self.multiplicities.remove(&(from, to));
+            self.edge_count -= 1;
+        }
+    }
+
+    /// Nodes that reach an origin now but would not if only the edges passing `keep` existed,
+    /// sorted ascending
+    /// # Arguments
+    /// * `keep` - Returns if the `(from, to)` edge is kept
+    pub(crate) fn disconnected_by(&self, keep: impl Fn(usize, usize) -> bool) -> Vec<usize> {
+        let connected = self.min_depths();
+
+        let mut reached = HashSet::new();
+        let mut queue: VecDeque<usize> = self
+            .origins()
+            .iter()
+            .copied()
+            .filter(|origin| self.nodes.contains(origin))
+            .collect();
+        reached.extend(queue.iter().copied());
+        while let Some(node) = queue.pop_front() {
+            for &predecessor in self.predecessors(node) {
+                if keep(predecessor, node) && reached.insert(predecessor) {
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+
+        let mut disconnected: Vec<usize> = connected
+            .into_keys()
+            .filter(|node| !reached.contains(node))
+            .collect();
+        disconnected.sort_unstable();
+        disconnected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DirectedAcyclicGraph, RemovalPolicy, RemovalReport, WouldDisconnect};
+
+    #[test]
+    fn test_removal_policies() {
+        let build = || {
+            let mut dag: DirectedAcyclicGraph =
+                vec![(2, 1), (3, 2), (4, 2), (4, 1)].into_iter().collect();
+            dag.add_node(9);
+            dag
+        };
+
+        let mut dag = build();
+        assert_eq!(
+            dag.remove_node_with(2, RemovalPolicy::Cascade),
+            Ok(RemovalReport {
+                nodes: vec![2, 3],
+                edges: vec![(2, 1), (3, 2), (4, 2)],
+            })
+        );
+        assert!(dag.contains_node(9) && dag.contains_edge(4, 1));
+
+        let mut dag = build();
+        let report = dag
+            .remove_node_with(2, RemovalPolicy::KeepIsolated)
+            .unwrap();
+        assert_eq!(report.nodes, [2]);
+        assert!(dag.contains_node(3));
+
+        let mut dag = build();
+        assert_eq!(
+            dag.remove_node_with(2, RemovalPolicy::RejectIfDisconnects),
+            Err(WouldDisconnect { nodes: vec![3] })
+        );
+        assert_eq!(dag.node_count(), 5);
+        assert_eq!(
+            dag.remove_node_with(3, RemovalPolicy::RejectIfDisconnects)
+                .map(|report| report.edges),
+            Ok(vec![(3, 2)])
+        );
+        assert!(dag
+            .remove_node_with(1, RemovalPolicy::Cascade)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_remove_edge_checked() {
+        let mut dag: DirectedAcyclicGraph =
+            vec![(2, 1), (3, 2), (3, 1), (4, 3)].into_iter().collect();
+
+        assert_eq!(
+            dag.remove_edge_checked(2, 1),
+            Err(WouldDisconnect { nodes: vec![2] })
+        );
+        assert_eq!(
+            dag.remove_edge_checked(4, 3),
+            Err(WouldDisconnect { nodes: vec![4] })
+        );
+        assert_eq!(dag.remove_edge_checked(3, 2), Ok(true));
+        assert_eq!(dag.remove_edge_checked(3, 2), Ok(false));
+        assert_eq!(
+            dag.remove_edge_checked(3, 1),
+            Err(WouldDisconnect { nodes: vec![3, 4] })
+        );
+        assert_eq!(dag.edge_count(), 3);
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+
+use crate::hash::HashMap;
+use crate::{BigUint, DirectedAcyclicGraph};
+
+/// Per-node row of `DirectedAcyclicGraph::node_report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRow {
+    /// Node id
+    pub id: usize,
+    /// Shortest depth, `None` if the node has no path to an origin
+    pub depth: Option<usize>,
+    /// Edges ending at this node, counted by edge multiplicity
+    pub in_references: usize,
+    /// Edges starting at this node, counted by edge multiplicity
+    pub out_references: usize,
+    /// Number of nodes referencing this node directly or transitively
+    pub descendant_count: usize,
+    /// Number of distinct paths to an origin, `None` if the graph is cyclic
+    pub path_count: Option<BigUint>,
+}
+
+impl DirectedAcyclicGraph {
+    /// One row per node in ascending id order, for exporting to a spreadsheet or data frame
+    /// Depths, path counts and descendant counts are computed up front in single passes,
+    /// only on cyclic graphs descendants are counted as rows are consumed
+    pub fn node_report(&self) -> impl Iterator<Item = NodeRow> + '_ {
+        let min_depths = self.min_depths();
+        let mut descendant_counts = self.cumulative_weights();
+        let mut path_counts = self
+            .topological_order()
+            .map(|order| self.origin_path_counts(&order));
+        let mut nodes: Vec<usize> = self.nodes().collect();
+        nodes.sort_unstable();
+
+        nodes.into_iter().map(move |node| NodeRow {
+            id: node,
+            depth: min_depths.get(&node).copied(),
+            in_references: self.in_references(node),
+            out_references: self.out_references(node),
+            descendant_count: descendant_counts
+                .as_mut()
+                .and_then(|counts| counts.remove(&node))
+                .unwrap_or_else(|| self.descendant_count(node)),
+            path_count: path_counts
+                .as_mut()
+                .map(|counts| counts.remove(&node).unwrap_or_default()),
+        })
+    }
+
+    /// Number of paths from every node to an origin, see `path_count`
+    /// # Arguments
+    /// * `order` - Topological order of the graph
+    pub(crate) fn origin_path_counts(&self, order: &[usize]) -> HashMap<usize, BigUint> {
+        // Parents first, so the counts of every parent are known
+        let mut counts: HashMap<usize, BigUint> = HashMap::with_capacity(order.len());
+        for &node in order.iter().rev() {
+            let count = if self.is_origin(node) {
+                BigUint::from(1u8)
+            } else {
+                self.neighbors(node)
+                    .iter()
+                    .map(|parent| &counts[parent])
+                    .sum()
+            };
+            counts.insert(node, count);
+        }
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, BigUint};
+
+    #[test]
+    fn test_node_report() {
+        let mut dag = sample_dag();
+        dag.add_edge_with_multiplicity(6, 1, 3);
+        let rows: Vec<_> = dag.node_report().collect();
+        assert_eq!(rows.len(), 6);
+        assert!(rows.windows(2).all(|pair| pair[0].id < pair[1].id));
+        for row in &rows {
+            assert_eq!(row.depth, dag.min_depth_of(row.id));
+            assert_eq!(row.in_references, dag.in_references(row.id));
+            assert_eq!(row.out_references, dag.out_references(row.id));
+            assert_eq!(row.descendant_count, dag.descendant_count(row.id));
+            assert_eq!(row.path_count, dag.path_count(row.id));
+        }
+        assert_eq!(rows[0].path_count, Some(BigUint::from(1u8)));
+        assert_eq!(rows[0].descendant_count, 5);
+        assert_eq!(rows[5].out_references, 4);
+
+        dag.add_edge(1, 5);
+        let cyclic: Vec<_> = dag.node_report().collect();
+        assert_eq!(cyclic[0].descendant_count, dag.descendant_count(1));
+        assert_eq!(cyclic[0].path_count, None);
+    }
+}
@@ -0,0 +1,231 @@
+use alloc::collections::BinaryHeap;
+use alloc::{format, string::ToString, vec, vec::Vec};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::{DirectedAcyclicGraph, Histogram, Metric};
+
+/// Two-sided z-score of the 95% confidence intervals
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// Sample mean of a per-node metric with a 95% confidence interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Estimate {
+    /// Mean over the sampled nodes
+    pub mean: f64,
+    /// Lower bound of the confidence interval
+    pub lower: f64,
+    /// Upper bound of the confidence interval
+    pub upper: f64,
+}
+
+impl Estimate {
+    /// Estimates the population mean with a normal approximation, the interval is corrected
+    /// for sampling without replacement and collapses once every node is sampled
+    /// Returns `None` for an empty sample, the interval is unbounded for a single value
+    /// # Arguments
+    /// * `values` - Values of the sampled nodes
+    /// * `population` - Number of nodes the sample was drawn from
+    fn of(values: &[f64], population: usize) -> Option<Estimate> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let margin = if values.len() >= population {
+            0.0
+        } else if values.len() < 2 {
+            f64::INFINITY
+        } else {
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let correction = (population as f64 - n) / (population as f64 - 1.0);
+            Z_95 * (variance / n * correction).sqrt()
+        };
+
+        Some(Estimate {
+            mean,
+            lower: mean - margin,
+            upper: mean + margin,
+        })
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        write!(
+            f,
+            "{:.*} (95% CI {:.*} to {:.*})",
+            precision, self.mean, precision, self.lower, precision, self.upper
+        )
+    }
+}
+
+/// Statistics estimated from a uniform node sample, computed by
+/// `DirectedAcyclicGraph::statistics_sampled`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampledStatistics {
+    /// Number of nodes, exact
+    pub node_count: usize,
+    /// Number of sampled nodes
+    pub sample_size: usize,
+    /// Estimated average shortest depth of the nodes with a path to an origin
+    pub avg_depth: Option<Estimate>,
+    /// Estimated average in-reference per node
+    pub avg_ref: Option<Estimate>,
+    /// Shortest depths of the sampled nodes, scale the counts by
+    /// `node_count / sample_size` to estimate the level sizes
+    pub depth_distribution: Histogram,
+    /// Sampled nodes without a path to an origin
+    pub unreachable_count: usize,
+}
+
+impl SampledStatistics {
+    /// Metrics estimated from the sample, in the order of `values`
+    pub const METRICS: [Metric; 3] = [Metric::NodeCount, Metric::AvgDepth, Metric::AvgRef];
+
+    /// Point estimates paired with their metric, the metrics that were not estimated are left out
+    pub fn values(&self) -> Vec<(Metric, Option<f64>)> {
+        vec![
+            (Metric::NodeCount, Some(self.node_count as f64)),
+            (Metric::AvgDepth, self.avg_depth.map(|e| e.mean)),
+            (Metric::AvgRef, self.avg_ref.map(|e| e.mean)),
+        ]
+    }
+}
+
+impl fmt::Display for SampledStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let estimate = |estimate: Option<Estimate>, precision: usize| {
+            estimate.map_or("-".to_string(), |e| format!("{:.*}", precision, e))
+        };
+        let depth = |depth: Option<usize>| depth.map_or("-".to_string(), |d| d.to_string());
+
+        writeln!(f, "NODES: {}", self.node_count)?;
+        writeln!(f, "SAMPLED NODES: {}", self.sample_size)?;
+        writeln!(f, "AVG DAG DEPTH: {}", estimate(self.avg_depth, 2))?;
+        writeln!(f, "AVG REF: {}", estimate(self.avg_ref, 3))?;
+        writeln!(
+            f,
+            "MEDIAN DEPTH: {}",
+            depth(self.depth_distribution.median())
+        )?;
+        writeln!(
+            f,
+            "P95 DEPTH: {}",
+            depth(self.depth_distribution.percentile(95.0))
+        )?;
+        write!(f, "SAMPLED UNREACHABLE: {}", self.unreachable_count)
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Estimates the average depth, average reference count and depth distribution from
+    /// `sample_size` nodes drawn uniformly without replacement, for graphs too large for
+    /// `statistics`
+    /// Only the depths of the sampled nodes are searched, the sample is the same for the same
+    /// seed and graph regardless of insertion order
+    /// # Arguments
+    /// * `sample_size` - Number of nodes to sample, every node if it exceeds the node count
+    /// * `seed` - Seed selecting the sample
+    pub fn statistics_sampled(&self, sample_size: usize, seed: u64) -> SampledStatistics {
+        let sample = self.sample_nodes(sample_size, seed);
+
+        let mut depths = Vec::with_capacity(sample.len());
+        let mut references = Vec::with_capacity(sample.len());
+        for &node in &sample {
+            if let Some(depth) = self.min_depth_of(node) {
+                depths.push(depth);
+            }
+            references.push(self.in_references(node) as f64);
+        }
+
+        // Depths are estimated over the reachable nodes, whose number is itself estimated
+        let reachable = if sample.is_empty() {
+            0
+        } else {
+            self.nodes.len() * depths.len() / sample.len()
+        };
+        let depth_values: Vec<f64> = depths.iter().map(|depth| *depth as f64).collect();
+
+        SampledStatistics {
+            node_count: self.nodes.len(),
+            sample_size: sample.len(),
+            avg_depth: Estimate::of(&depth_values, reachable.max(depths.len())),
+            avg_ref: Estimate::of(&references, self.nodes.len()),
+            unreachable_count: sample.len() - depths.len(),
+            depth_distribution: Histogram::from_values(depths),
+        }
+    }
+
+    /// Uniform sample without replacement, the nodes with the smallest seeded hashes of their id
+    /// # Arguments
+    /// * `sample_size` - Number of nodes to sample
+    /// * `seed` - Seed of the hashes
+    fn sample_nodes(&self, sample_size: usize, seed: u64) -> Vec<usize> {
+        // Max heap of the smallest keys seen so far
+        let mut heap = BinaryHeap::with_capacity(sample_size.min(self.nodes.len()) + 1);
+        for node in self.nodes() {
+            let key = (mix(node as u64 ^ mix(seed)), node);
+            if heap.len() < sample_size {
+                heap.push(key);
+            } else if heap.peek().is_some_and(|largest| key < *largest) {
+                heap.pop();
+                heap.push(key);
+            }
+        }
+
+        let mut sample: Vec<usize> = heap.into_iter().map(|(_, node)| node).collect();
+        sample.sort_unstable();
+        sample
+    }
+}
+
+/// SplitMix64 finalizer, spreads the bits of `value` over the whole word
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_statistics_sampled() {
+        let mut dag = DirectedAcyclicGraph::new();
+        for node in 2..=2000 {
+            dag.add_edge(node, node / 2);
+        }
+
+        let sampled = dag.statistics_sampled(200, 7);
+        assert_eq!(sampled.sample_size, 200);
+        assert_eq!(sampled, dag.statistics_sampled(200, 7));
+        assert_ne!(sampled, dag.statistics_sampled(200, 8));
+
+        let avg_depth = sampled.avg_depth.unwrap();
+        let exact = dag.avg_depth().unwrap();
+        assert!(avg_depth.lower < avg_depth.mean && avg_depth.mean < avg_depth.upper);
+        assert!(avg_depth.lower <= exact && exact <= avg_depth.upper);
+        assert_eq!(sampled.depth_distribution.total(), 200);
+
+        // Sampling every node is exact
+        let full = dag.statistics_sampled(5000, 7);
+        assert_eq!(full.sample_size, 2000);
+        let avg_ref = full.avg_ref.unwrap();
+        assert_eq!((avg_ref.lower, avg_ref.upper), (avg_ref.mean, avg_ref.mean));
+        assert!((avg_ref.mean - dag.avg_ref().unwrap()).abs() < 1e-12);
+        assert_eq!(
+            DirectedAcyclicGraph::new()
+                .statistics_sampled(10, 0)
+                .avg_ref,
+            None
+        );
+    }
+}
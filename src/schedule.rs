@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use crate::hash::HashMap;
+use crate::DirectedAcyclicGraph;
+
+/// As soon as possible schedule of a DAG read as a build or task graph,
+/// computed by `DirectedAcyclicGraph::schedule`
+/// Every node is a task that depends on the nodes it references, so the origins run first
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    /// Earliest time every task can start, once all of its dependencies finished
+    pub start_times: HashMap<usize, f64>,
+    /// Time the last task finishes
+    pub makespan: f64,
+    /// Chain of dependent tasks that determines the makespan, in execution order
+    pub critical_path: Vec<usize>,
+    /// Peak number of tasks running at the same time
+    pub max_parallelism: usize,
+}
+
+impl DirectedAcyclicGraph {
+    /// Duration of the task `node`, read from its `duration` attribute
+    /// Missing, unparsable or negative durations count as 1.0
+    /// # Arguments
+    /// * `node` - Node id of the task
+    pub fn task_duration(&self, node: usize) -> f64 {
+        self.node_attr(node, "duration")
+            .and_then(|duration| duration.parse::<f64>().ok())
+            .filter(|duration| *duration >= 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Schedules every task as early as its dependencies allow, using `task_duration`
+    /// Unlike `critical_path`, which weighs edges, durations are attached to the tasks
+    /// Returns `None` if the graph is cyclic
+    pub fn schedule(&self) -> Option<Schedule> {
+        let order = self.topological_order()?;
+
+        // Dependencies come after their dependents in the order, so walk it backwards
+        let mut start_times: HashMap<usize, f64> = HashMap::with_capacity(order.len());
+        let mut blocking: HashMap<usize, usize> = HashMap::new();
+        for &node in order.iter().rev() {
+            let mut start = 0.0;
+            for &dependency in self.neighbors(node) {
+                let finish = start_times[&dependency] + self.task_duration(dependency);
+                if finish > start || !blocking.contains_key(&node) {
+                    start = finish;
+                    blocking.insert(node, dependency);
+                }
+            }
+            start_times.insert(node, start);
+        }
+
+        let finish = |node: &usize| start_times[node] + self.task_duration(*node);
+        let last = order
+            .iter()
+            .copied()
+            .max_by(|a, b| finish(a).total_cmp(&finish(b)).then(b.cmp(a)));
+        let makespan = last.as_ref().map_or(0.0, finish);
+
+        let mut critical_path: Vec<usize> = last.into_iter().collect();
+        while let Some(dependency) = critical_path.last().and_then(|node| blocking.get(node)) {
+            critical_path.push(*dependency);
+        }
+        critical_path.reverse();
+
+        // Sweep over start and finish events, finishing before starting on ties
+        // Zero-length tasks never overlap anything and would finish before their own start
+        let mut events: Vec<(f64, bool)> = Vec::with_capacity(2 * order.len());
+        for node in &order {
+            if self.task_duration(*node) > 0.0 {
+                events.push((start_times[node], true));
+                events.push((finish(node), false));
+            }
+        }
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let mut running: usize = 0;
+        let mut max_parallelism = 0;
+        for (_, starts) in events {
+            if starts {
+                running += 1;
+                max_parallelism = max_parallelism.max(running);
+            } else {
+                running -= 1;
+            }
+        }
+
+        Some(Schedule {
+            start_times,
+            makespan,
+            critical_path,
+            max_parallelism,
+        })
+    }
+
+    /// Earliest start time of every task, see `schedule`
+    /// Returns `None` if the graph is cyclic
+    pub fn earliest_start_times(&self) -> Option<HashMap<usize, f64>> {
+        self.schedule().map(|schedule| schedule.start_times)
+    }
+
+    /// Peak number of tasks running at the same time, see `schedule`
+    /// Returns `None` if the graph is cyclic
+    pub fn max_parallelism(&self) -> Option<usize> {
+        self.schedule().map(|schedule| schedule.max_parallelism)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_schedule() {
+        let mut dag = sample_dag();
+        let schedule = dag.schedule().unwrap();
+        assert_eq!(schedule.makespan, dag.max_depth() as f64);
+        assert_eq!(schedule.critical_path, [1, 2, 3, 6, 5]);
+        assert_eq!(schedule.start_times[&4], 2.0);
+        assert_eq!(schedule.max_parallelism, 2);
+
+        dag.set_node_attr(4, "duration", "10");
+        dag.set_node_attr(3, "duration", "0.5");
+        let schedule = dag.schedule().unwrap();
+        assert_eq!(schedule.makespan, 12.0);
+        assert_eq!(schedule.critical_path, [1, 2, 4]);
+        assert_eq!(schedule.start_times[&5], 3.5);
+        assert_eq!(dag.max_parallelism(), Some(2));
+
+        let mut pair = DirectedAcyclicGraph::new();
+        pair.add_edge(2, 1);
+        pair.set_node_attr(1, "duration", "0");
+        let schedule = pair.schedule().unwrap();
+        assert_eq!(schedule.makespan, 1.0);
+        assert_eq!(schedule.max_parallelism, 1);
+    }
+}
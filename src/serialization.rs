@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::DirectedAcyclicGraph;
+
+/// Serialized form of a DAG, everything is sorted so the output is stable
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "DirectedAcyclicGraph")]
+struct Repr {
+    origins: Vec<usize>,
+    nodes: Vec<usize>,
+    edges: Vec<(usize, usize)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    weights: Vec<(usize, usize, f64)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    multiplicities: Vec<(usize, usize, usize)>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    attributes: BTreeMap<usize, BTreeMap<String, String>>,
+}
+
+impl Serialize for DirectedAcyclicGraph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes: Vec<usize> = self.nodes().collect();
+        nodes.sort_unstable();
+
+        let mut edges: Vec<(usize, usize)> = self.edges().collect();
+        edges.sort_unstable();
+
+        let mut weights: Vec<(usize, usize, f64)> = self
+            .weights
+            .iter()
+            .map(|((from, to), weight)| (*from, *to, *weight))
+            .collect();
+        weights.sort_unstable_by_key(|(from, to, _)| (*from, *to));
+
+        let mut multiplicities: Vec<(usize, usize, usize)> = self
+            .multiplicities
+            .iter()
+            .map(|((from, to), multiplicity)| (*from, *to, *multiplicity))
+            .collect();
+        multiplicities.sort_unstable();
+
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(node, attributes)| {
+                let attributes = attributes
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+                (*node, attributes)
+            })
+            .collect();
+
+        Repr {
+            origins: self.origins.clone(),
+            nodes,
+            edges,
+            weights,
+            multiplicities,
+            attributes,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DirectedAcyclicGraph {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+        if repr.origins.is_empty() {
+            return Err(D::Error::custom("a DAG needs at least one origin"));
+        }
+
+        let mut dag = DirectedAcyclicGraph::with_origins(repr.origins);
+        dag.nodes.extend(repr.nodes);
+        for (from, to) in repr.edges {
+            if from == to {
+                return Err(D::Error::custom(format!("self-referencing edge {}", from)));
+            }
+            dag.add_edge(from, to);
+        }
+        for (from, to, weight) in repr.weights {
+            if dag.edge_weight(from, to).is_none() {
+                return Err(D::Error::custom(format!(
+                    "weight given for missing edge {} -> {}",
+                    from, to
+                )));
+            }
+            dag.add_weighted_edge(from, to, weight);
+        }
+        for (from, to, multiplicity) in repr.multiplicities {
+            if dag.edge_multiplicity(from, to).is_none() {
+                return Err(D::Error::custom(format!(
+                    "multiplicity given for missing edge {} -> {}",
+                    from, to
+                )));
+            }
+            dag.add_edge_with_multiplicity(from, to, multiplicity);
+        }
+        for (node, attributes) in repr.attributes {
+            for (key, value) in attributes {
+                if !dag.set_node_attr(node, key, value) {
+                    return Err(D::Error::custom(format!(
+                        "attributes given for missing node {}",
+                        node
+                    )));
+                }
+            }
+        }
+
+        Ok(dag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(3, 1);
+        dag.add_weighted_edge(2, 1, 0.5);
+        dag.add_edge(3, 2);
+        dag.set_node_attr(3, "label", "tip");
+
+        let json = serde_json::to_string(&dag).unwrap();
+        assert_eq!(
+            json,
+            r#"{"origins":[1],"nodes":[1,2,3],"edges":[[2,1],[3,1],[3,2]],"weights":[[2,1,0.5]],"attributes":{"3":{"label":"tip"}}}"#
+        );
+
+        let parsed: DirectedAcyclicGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+        let missing_edge = r#"{"origins":[1],"nodes":[1,2],"edges":[],"weights":[[2,1,0.5]]}"#;
+        assert!(serde_json::from_str::<DirectedAcyclicGraph>(missing_edge).is_err());
+    }
+}
@@ -0,0 +1,668 @@
+use alloc::collections::BTreeMap;
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::centrality::{PAGERANK_DAMPING, PAGERANK_ITERATIONS};
+use crate::depth::avg_paths_per_length;
+use crate::hash::HashMap;
+use crate::{average, DagView, DirectedAcyclicGraph, Histogram, UnknownMetric};
+
+/// Summary of every statistic of a DAG, computed by `DirectedAcyclicGraph::statistics`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// Number of nodes
+    pub node_count: usize,
+    /// Number of edges
+    pub edge_count: usize,
+    /// Average shortest depth of all nodes, see `DirectedAcyclicGraph::avg_depth`
+    pub avg_depth: Option<f64>,
+    /// Longest path to the origin counted in nodes, see `DirectedAcyclicGraph::max_depth`
+    pub max_depth: usize,
+    /// See `DirectedAcyclicGraph::avg_node_per_depth`
+    pub avg_node_per_depth: Option<f64>,
+    /// Average in-reference per node
+    pub avg_ref: Option<f64>,
+    /// See `DirectedAcyclicGraph::depth_spread`
+    pub depth_spread: Option<Spread>,
+    /// See `DirectedAcyclicGraph::node_per_depth_spread`
+    pub node_per_depth_spread: Option<Spread>,
+    /// See `DirectedAcyclicGraph::ref_spread`
+    pub ref_spread: Option<Spread>,
+    /// Average out-reference per node
+    pub avg_out_ref: Option<f64>,
+    /// Largest number of nodes sharing the same shortest depth
+    pub width: usize,
+    /// Number of tips, the nodes no other node references
+    pub tip_count: usize,
+    /// Average shortest depth of the tips, see `DirectedAcyclicGraph::avg_tip_depth`
+    pub avg_tip_depth: Option<f64>,
+    /// Number of nodes without a path to an origin, they are skipped by the depth statistics
+    pub unreachable_count: usize,
+    /// See `DirectedAcyclicGraph::density`
+    pub density: Option<f64>,
+    /// See `DirectedAcyclicGraph::avg_branching_factor`
+    pub avg_branching_factor: Option<f64>,
+    /// See `DirectedAcyclicGraph::leaf_fraction`
+    pub leaf_fraction: Option<f64>,
+    /// See `DirectedAcyclicGraph::in_degree_gini`
+    pub in_degree_gini: Option<f64>,
+    /// See `DirectedAcyclicGraph::in_degree_entropy`
+    pub in_degree_entropy: Option<f64>,
+}
+
+/// Range and dispersion of the values behind one of the averages of `Statistics`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spread {
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+    /// Population variance
+    pub variance: f64,
+    /// Population standard deviation, the square root of `variance`
+    pub std_dev: f64,
+}
+
+impl Spread {
+    /// Spread of values given by their number of occurrences, `None` if there are none
+    /// The values are visited in ascending order so the result does not depend on the map
+    /// # Arguments
+    /// * `counts` - Occurrences keyed by value
+    pub(crate) fn of_counts(counts: &HashMap<usize, usize>) -> Option<Spread> {
+        let mut counts: Vec<(f64, f64)> = counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(value, count)| (*value as f64, *count as f64))
+            .collect();
+        counts.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+        Spread::of_weighted(&counts)
+    }
+
+    /// Spread of the path counts per length, see `DirectedAcyclicGraph::avg_node_per_depth`
+    /// # Arguments
+    /// * `counts` - Output of `DirectedAcyclicGraph::paths_per_length`
+    pub(crate) fn of_path_counts(counts: &BTreeMap<usize, u128>) -> Option<Spread> {
+        let mut counts: Vec<(f64, f64)> =
+            counts.values().map(|count| (*count as f64, 1.0)).collect();
+        counts.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+        Spread::of_weighted(&counts)
+    }
+
+    /// Spread of values sorted ascending and paired with their weight
+    /// # Arguments
+    /// * `values` - Value and weight pairs
+    fn of_weighted(values: &[(f64, f64)]) -> Option<Spread> {
+        let (min, max) = (values.first()?.0, values.last()?.0);
+        let total: f64 = values.iter().map(|(_, weight)| weight).sum();
+        let mean = values
+            .iter()
+            .map(|(value, weight)| value * weight)
+            .sum::<f64>()
+            / total;
+        let variance = values
+            .iter()
+            .map(|(value, weight)| (value - mean).powi(2) * weight)
+            .sum::<f64>()
+            / total;
+
+        Some(Spread {
+            min,
+            max,
+            variance,
+            std_dev: variance.sqrt(),
+        })
+    }
+}
+
+/// Per-node aggregates of `Statistics`, can be computed in chunks and merged
+#[derive(Default)]
+struct NodeSummary {
+    total_depth: usize,
+    level_sizes: HashMap<usize, usize>,
+    ref_counts: HashMap<usize, usize>,
+    tip_count: usize,
+    reachable_tip_count: usize,
+    total_tip_depth: usize,
+    unreachable_count: usize,
+}
+
+impl NodeSummary {
+    /// Adds a single node to the summary
+    /// # Arguments
+    /// * `graph` - Graph the node belongs to
+    /// * `min_depths` - Shortest depth table of the graph
+    /// * `node` - Node id to add
+    fn add<G: DagView + ?Sized>(
+        mut self,
+        graph: &G,
+        min_depths: &HashMap<usize, usize>,
+        node: usize,
+    ) -> Self {
+        *self
+            .ref_counts
+            .entry(graph.in_references(node))
+            .or_insert(0) += 1;
+        let is_tip = graph.child_ids(node).next().is_none();
+        if is_tip {
+            self.tip_count += 1;
+        }
+
+        let depth = match min_depths.get(&node) {
+            Some(depth) => *depth,
+            None => {
+                self.unreachable_count += 1;
+                return self;
+            }
+        };
+        self.total_depth += depth;
+        *self.level_sizes.entry(depth).or_insert(0) += 1;
+        if is_tip {
+            self.reachable_tip_count += 1;
+            self.total_tip_depth += depth;
+        }
+
+        self
+    }
+
+    /// Combines two summaries of disjoint node sets
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    fn merge(mut self, other: NodeSummary) -> Self {
+        self.total_depth += other.total_depth;
+        for (depth, size) in other.level_sizes {
+            *self.level_sizes.entry(depth).or_insert(0) += size;
+        }
+        for (references, count) in other.ref_counts {
+            *self.ref_counts.entry(references).or_insert(0) += count;
+        }
+        self.tip_count += other.tip_count;
+        self.reachable_tip_count += other.reachable_tip_count;
+        self.total_tip_depth += other.total_tip_depth;
+        self.unreachable_count += other.unreachable_count;
+
+        self
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Computes every statistic at once, sharing the depth tables and topological order
+    /// between them instead of retraversing the graph for each one
+    pub fn statistics(&self) -> Statistics {
+        statistics_of(self, self.path_statistics())
+    }
+
+    /// Same as `statistics` but computes the per-node metrics on the rayon thread pool,
+    /// concurrently with the topological pass
+    #[cfg(feature = "rayon")]
+    pub fn par_statistics(&self) -> Statistics {
+        use rayon::prelude::*;
+
+        let (summary, path_statistics) = rayon::join(
+            || {
+                let min_depths = self.min_depths();
+                let nodes: Vec<usize> = self.nodes().collect();
+                nodes
+                    .par_iter()
+                    .fold(NodeSummary::default, |summary, node| {
+                        summary.add(self, &min_depths, *node)
+                    })
+                    .reduce(NodeSummary::default, NodeSummary::merge)
+            },
+            || self.path_statistics(),
+        );
+
+        summarize(self, summary, path_statistics)
+    }
+
+    /// Max depth and path counts per length, sharing one topological order
+    fn path_statistics(&self) -> (usize, BTreeMap<usize, u128>) {
+        match self.topological_order() {
+            Some(order) => (
+                self.max_depths(&order)
+                    .values()
+                    .max()
+                    .map_or(0, |depth| depth + 1),
+                self.path_length_counts(&order),
+            ),
+            None => (self.max_depth(), self.paths_per_length()),
+        }
+    }
+}
+
+/// Computes every statistic of any graph representation, see `DirectedAcyclicGraph::statistics`
+/// # Arguments
+/// * `graph` - Graph to measure
+/// * `path_statistics` - Longest path in nodes and path counts per length of the graph
+pub(crate) fn statistics_of<G: DagView + ?Sized>(
+    graph: &G,
+    path_statistics: (usize, BTreeMap<usize, u128>),
+) -> Statistics {
+    let min_depths = crate::depth::min_depths(graph);
+    let summary = graph
+        .node_ids()
+        .fold(NodeSummary::default(), |summary, node| {
+            summary.add(graph, &min_depths, node)
+        });
+
+    summarize(graph, summary, path_statistics)
+}
+
+/// Assembles the final statistics
+/// # Arguments
+/// * `graph` - Graph the summary was computed on
+/// * `summary` - Per-node aggregates over every node
+/// * `path_statistics` - Longest path in nodes and path counts per length of the graph
+fn summarize<G: DagView + ?Sized>(
+    graph: &G,
+    summary: NodeSummary,
+    path_statistics: (usize, BTreeMap<usize, u128>),
+) -> Statistics {
+    let node_count = graph.node_count();
+    let (max_depth, path_counts) = path_statistics;
+    let references = Histogram::from_counts(&summary.ref_counts);
+
+    Statistics {
+        node_count,
+        edge_count: graph.edge_count(),
+        avg_depth: average(
+            summary.total_depth as f64,
+            node_count - summary.unreachable_count,
+        ),
+        max_depth,
+        avg_node_per_depth: avg_paths_per_length(&path_counts),
+        avg_ref: average(graph.reference_count() as f64, node_count),
+        depth_spread: Spread::of_counts(&summary.level_sizes),
+        node_per_depth_spread: Spread::of_path_counts(&path_counts),
+        ref_spread: Spread::of_counts(&summary.ref_counts),
+        avg_out_ref: average(graph.reference_count() as f64, node_count),
+        width: summary.level_sizes.values().max().copied().unwrap_or(0),
+        tip_count: summary.tip_count,
+        avg_tip_depth: average(summary.total_tip_depth as f64, summary.reachable_tip_count),
+        unreachable_count: summary.unreachable_count,
+        density: graph.density(),
+        avg_branching_factor: graph.avg_branching_factor(),
+        leaf_fraction: average(summary.tip_count as f64, node_count),
+        in_degree_gini: references.gini(),
+        in_degree_entropy: references.share_entropy(),
+    }
+}
+
+impl Statistics {
+    /// Every statistic paired with its metric, in display order
+    /// Counts are converted to `f64`, undefined averages are `None`
+    pub fn values(&self) -> Vec<(Metric, Option<f64>)> {
+        Metric::ALL
+            .iter()
+            .map(|metric| (*metric, self.value(*metric)))
+            .collect()
+    }
+
+    /// Value of a single metric, `None` for the metrics of `Metric::ON_DEMAND` it does not hold
+    /// # Arguments
+    /// * `metric` - Metric to read
+    pub fn value(&self, metric: Metric) -> Option<f64> {
+        match metric {
+            Metric::NodeCount => Some(self.node_count as f64),
+            Metric::EdgeCount => Some(self.edge_count as f64),
+            Metric::AvgDepth => self.avg_depth,
+            Metric::DepthStdDev => self.depth_spread.map(|spread| spread.std_dev),
+            Metric::AvgNodePerDepth => self.avg_node_per_depth,
+            Metric::NodePerDepthStdDev => self.node_per_depth_spread.map(|spread| spread.std_dev),
+            Metric::AvgRef => self.avg_ref,
+            Metric::RefStdDev => self.ref_spread.map(|spread| spread.std_dev),
+            Metric::AvgOutRef => self.avg_out_ref,
+            Metric::MaxDepth => Some(self.max_depth as f64),
+            Metric::Width => Some(self.width as f64),
+            Metric::TipCount => Some(self.tip_count as f64),
+            Metric::AvgTipDepth => self.avg_tip_depth,
+            Metric::UnreachableCount => Some(self.unreachable_count as f64),
+            Metric::Density => self.density,
+            Metric::AvgBranchingFactor => self.avg_branching_factor,
+            Metric::LeafFraction => self.leaf_fraction,
+            Metric::InDegreeGini => self.in_degree_gini,
+            Metric::InDegreeEntropy => self.in_degree_entropy,
+            Metric::PageRank | Metric::Radius | Metric::Diameter => None,
+        }
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self
+            .values()
+            .into_iter()
+            .map(|(metric, value)| format!("{}: {}", metric.label(), metric.format(value)))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// A single statistic, computable on its own with `DirectedAcyclicGraph::metric`
+/// Parsed from its snake case or kebab case name, e.g. `max-depth`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// Number of nodes
+    NodeCount,
+    /// Number of edges
+    EdgeCount,
+    /// See `DirectedAcyclicGraph::avg_depth`
+    AvgDepth,
+    /// Standard deviation of the shortest depths, see `DirectedAcyclicGraph::depth_spread`
+    DepthStdDev,
+    /// See `DirectedAcyclicGraph::avg_node_per_depth`
+    AvgNodePerDepth,
+    /// Standard deviation of the path counts, see `DirectedAcyclicGraph::node_per_depth_spread`
+    NodePerDepthStdDev,
+    /// See `DirectedAcyclicGraph::avg_ref`
+    AvgRef,
+    /// Standard deviation of the in-references, see `DirectedAcyclicGraph::ref_spread`
+    RefStdDev,
+    /// See `DirectedAcyclicGraph::avg_out_ref`
+    AvgOutRef,
+    /// See `DirectedAcyclicGraph::max_depth`
+    MaxDepth,
+    /// See `DirectedAcyclicGraph::width`
+    Width,
+    /// See `DirectedAcyclicGraph::tip_count`
+    TipCount,
+    /// See `DirectedAcyclicGraph::avg_tip_depth`
+    AvgTipDepth,
+    /// Number of nodes without a path to an origin
+    UnreachableCount,
+    /// See `DirectedAcyclicGraph::density`
+    Density,
+    /// See `DirectedAcyclicGraph::avg_branching_factor`
+    AvgBranchingFactor,
+    /// See `DirectedAcyclicGraph::leaf_fraction`
+    LeafFraction,
+    /// See `DirectedAcyclicGraph::in_degree_gini`
+    InDegreeGini,
+    /// See `DirectedAcyclicGraph::in_degree_entropy`
+    InDegreeEntropy,
+    /// Highest `DirectedAcyclicGraph::pagerank` of any node with a damping of 0.85
+    PageRank,
+    /// See `DirectedAcyclicGraph::radius`
+    Radius,
+    /// See `DirectedAcyclicGraph::undirected_diameter`
+    Diameter,
+}
+
+impl Metric {
+    /// Every metric in display order
+    pub const ALL: [Metric; 19] = [
+        Metric::NodeCount,
+        Metric::EdgeCount,
+        Metric::AvgDepth,
+        Metric::DepthStdDev,
+        Metric::AvgNodePerDepth,
+        Metric::NodePerDepthStdDev,
+        Metric::AvgRef,
+        Metric::RefStdDev,
+        Metric::AvgOutRef,
+        Metric::MaxDepth,
+        Metric::Width,
+        Metric::TipCount,
+        Metric::AvgTipDepth,
+        Metric::UnreachableCount,
+        Metric::Density,
+        Metric::AvgBranchingFactor,
+        Metric::LeafFraction,
+        Metric::InDegreeGini,
+        Metric::InDegreeEntropy,
+    ];
+
+    /// Metrics that are only computed when asked for, they are not part of `Statistics`
+    pub const ON_DEMAND: [Metric; 3] = [Metric::PageRank, Metric::Radius, Metric::Diameter];
+
+    /// Snake case name, used as key in machine readable output
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::NodeCount => "node_count",
+            Metric::EdgeCount => "edge_count",
+            Metric::AvgDepth => "avg_depth",
+            Metric::DepthStdDev => "depth_stddev",
+            Metric::AvgNodePerDepth => "avg_node_per_depth",
+            Metric::NodePerDepthStdDev => "node_per_depth_stddev",
+            Metric::AvgRef => "avg_ref",
+            Metric::RefStdDev => "ref_stddev",
+            Metric::AvgOutRef => "avg_out_ref",
+            Metric::MaxDepth => "max_depth",
+            Metric::Width => "width",
+            Metric::TipCount => "tip_count",
+            Metric::AvgTipDepth => "avg_tip_depth",
+            Metric::UnreachableCount => "unreachable_count",
+            Metric::Density => "density",
+            Metric::AvgBranchingFactor => "avg_branching_factor",
+            Metric::LeafFraction => "leaf_fraction",
+            Metric::InDegreeGini => "in_degree_gini",
+            Metric::InDegreeEntropy => "in_degree_entropy",
+            Metric::PageRank => "pagerank",
+            Metric::Radius => "radius",
+            Metric::Diameter => "diameter",
+        }
+    }
+
+    /// Upper case label used by the `Statistics` display
+    pub fn label(self) -> &'static str {
+        match self {
+            Metric::NodeCount => "NODES",
+            Metric::EdgeCount => "EDGES",
+            Metric::AvgDepth => "AVG DAG DEPTH",
+            Metric::DepthStdDev => "DAG DEPTH STDDEV",
+            Metric::AvgNodePerDepth => "AVG NODES PER DEPTH",
+            Metric::NodePerDepthStdDev => "NODES PER DEPTH STDDEV",
+            Metric::AvgRef => "AVG REF",
+            Metric::RefStdDev => "REF STDDEV",
+            Metric::AvgOutRef => "AVG OUT REF",
+            Metric::MaxDepth => "MAX DEPTH",
+            Metric::Width => "WIDTH",
+            Metric::TipCount => "TIPS",
+            Metric::AvgTipDepth => "AVG TIP DEPTH",
+            Metric::UnreachableCount => "UNREACHABLE",
+            Metric::Density => "DENSITY",
+            Metric::AvgBranchingFactor => "AVG BRANCHING FACTOR",
+            Metric::LeafFraction => "LEAF FRACTION",
+            Metric::InDegreeGini => "IN-DEGREE GINI",
+            Metric::InDegreeEntropy => "IN-DEGREE ENTROPY",
+            Metric::PageRank => "MAX PAGERANK",
+            Metric::Radius => "RADIUS",
+            Metric::Diameter => "DIAMETER",
+        }
+    }
+
+    /// Formats a value of this metric for display, undefined values are written as `-`
+    /// # Arguments
+    /// * `value` - Value of this metric
+    pub fn format(self, value: Option<f64>) -> String {
+        let precision = match self {
+            Metric::AvgDepth | Metric::AvgNodePerDepth | Metric::AvgTipDepth => 2,
+            Metric::DepthStdDev | Metric::NodePerDepthStdDev => 2,
+            Metric::AvgRef | Metric::AvgOutRef | Metric::RefStdDev => 3,
+            Metric::AvgBranchingFactor | Metric::LeafFraction => 3,
+            Metric::InDegreeGini | Metric::InDegreeEntropy => 3,
+            Metric::Density => 6,
+            Metric::PageRank => 4,
+            _ => 0,
+        };
+
+        match value {
+            Some(value) => format!("{:.*}", precision, value),
+            None => "-".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Metric {
+    type Err = UnknownMetric;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let normalized = name.trim().replace('-', "_").to_ascii_lowercase();
+        Metric::ALL
+            .iter()
+            .chain(Metric::ON_DEMAND.iter())
+            .find(|metric| metric.name() == normalized)
+            .copied()
+            .ok_or_else(|| UnknownMetric(name.to_string()))
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Computes a single metric, running only the traversals it needs
+    /// # Arguments
+    /// * `metric` - Metric to compute
+    pub fn metric(&self, metric: Metric) -> Option<f64> {
+        match metric {
+            Metric::NodeCount => Some(self.node_count() as f64),
+            Metric::EdgeCount => Some(self.edge_count() as f64),
+            Metric::AvgDepth => self.avg_depth(),
+            Metric::DepthStdDev => self.depth_spread().map(|spread| spread.std_dev),
+            Metric::AvgNodePerDepth => self.avg_node_per_depth(),
+            Metric::NodePerDepthStdDev => self.node_per_depth_spread().map(|spread| spread.std_dev),
+            Metric::AvgRef => self.avg_ref(),
+            Metric::RefStdDev => self.ref_spread().map(|spread| spread.std_dev),
+            Metric::AvgOutRef => self.avg_out_ref(),
+            Metric::MaxDepth => Some(self.max_depth() as f64),
+            Metric::Width => Some(self.width() as f64),
+            Metric::TipCount => Some(self.tip_count() as f64),
+            Metric::AvgTipDepth => self.avg_tip_depth(),
+            Metric::UnreachableCount => Some(self.unreachable_from_origin().len() as f64),
+            Metric::Density => self.density(),
+            Metric::AvgBranchingFactor => self.avg_branching_factor(),
+            Metric::LeafFraction => self.leaf_fraction(),
+            Metric::InDegreeGini => self.in_degree_gini(),
+            Metric::InDegreeEntropy => self.in_degree_entropy(),
+            Metric::PageRank => self
+                .pagerank(PAGERANK_DAMPING, PAGERANK_ITERATIONS)
+                .into_values()
+                .reduce(f64::max),
+            Metric::Radius => self.radius().map(|radius| radius as f64),
+            Metric::Diameter => self.undirected_diameter().map(|diameter| diameter as f64),
+        }
+    }
+
+    /// Computes the given metrics, see `metric`
+    /// # Arguments
+    /// * `metrics` - Metrics to compute, in output order
+    pub fn metrics(&self, metrics: &[Metric]) -> Vec<(Metric, Option<f64>)> {
+        metrics
+            .iter()
+            .map(|metric| (*metric, self.metric(*metric)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph, Metric};
+
+    #[test]
+    fn test_statistics_match_individual_metrics() {
+        let dag = sample_dag();
+        let statistics = dag.statistics();
+
+        assert_eq!(statistics.node_count, 6);
+        assert_eq!(statistics.edge_count, 7);
+        assert_eq!(statistics.avg_depth, dag.avg_depth());
+        assert_eq!(statistics.max_depth, dag.max_depth());
+        assert_eq!(statistics.avg_node_per_depth, dag.avg_node_per_depth());
+        assert_eq!(statistics.avg_ref, dag.avg_ref());
+        assert_eq!(statistics.avg_out_ref, dag.avg_out_ref());
+        assert_eq!(statistics.width, 3);
+        assert_eq!(statistics.tip_count, 2);
+        assert_eq!(statistics.avg_tip_depth, dag.avg_tip_depth());
+        assert_eq!(statistics.unreachable_count, 0);
+        assert_eq!(statistics.density, Some(7.0 / 15.0));
+        assert_eq!(statistics.avg_branching_factor, Some(7.0 / 4.0));
+        assert_eq!(statistics.leaf_fraction, Some(2.0 / 6.0));
+        assert_eq!(statistics.depth_spread, dag.depth_spread());
+        assert_eq!(
+            statistics.node_per_depth_spread,
+            dag.node_per_depth_spread()
+        );
+        assert_eq!(statistics.ref_spread, dag.ref_spread());
+        assert_eq!(statistics.in_degree_gini, dag.in_degree_gini());
+        assert_eq!(statistics.in_degree_entropy, dag.in_degree_entropy());
+
+        assert_eq!(statistics.to_string().lines().next(), Some("NODES: 6"));
+        assert_eq!(statistics.values(), dag.metrics(&Metric::ALL));
+        assert_eq!(
+            statistics.values().len(),
+            statistics.to_string().lines().count()
+        );
+    }
+
+    #[test]
+    fn test_metric_names() {
+        for metric in Metric::ALL.iter().chain(Metric::ON_DEMAND.iter()) {
+            assert_eq!(metric.name().parse::<Metric>(), Ok(*metric));
+        }
+        assert_eq!("max-depth".parse::<Metric>(), Ok(Metric::MaxDepth));
+        assert!("depth".parse::<Metric>().is_err());
+        assert_eq!(Metric::AvgRef.format(Some(1.0)), "1.000");
+        assert_eq!(Metric::AvgDepth.format(None), "-");
+    }
+
+    #[test]
+    fn test_statistics_of_trivial_graphs() {
+        let empty = DirectedAcyclicGraph::new().statistics();
+        assert_eq!(empty.node_count, 0);
+        assert_eq!(empty.avg_depth, None);
+        assert_eq!(empty.avg_node_per_depth, None);
+        assert_eq!(empty.avg_ref, None);
+        assert_eq!(empty.max_depth, 0);
+        assert!(empty.to_string().contains("AVG DAG DEPTH: -"));
+
+        let mut single = DirectedAcyclicGraph::new();
+        single.add_edge(2, 1);
+        single.remove_edge(2, 1);
+        assert_eq!(single.avg_depth(), Some(0.0));
+        assert_eq!(single.avg_ref(), Some(0.0));
+        assert_eq!(single.avg_node_per_depth(), None);
+        assert_eq!(single.statistics().max_depth, 1);
+    }
+
+    #[test]
+    fn test_spreads() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 2);
+        dag.add_edge(4, 1);
+
+        let depths = dag.depth_spread().unwrap();
+        assert_eq!((depths.min, depths.max), (0.0, 2.0));
+        assert_eq!(depths.variance, 0.5);
+        assert_eq!(depths.std_dev, 0.5f64.sqrt());
+
+        let references = dag.ref_spread().unwrap();
+        assert_eq!((references.min, references.max), (0.0, 2.0));
+        assert_eq!(references.variance, 0.6875);
+
+        assert_eq!(DirectedAcyclicGraph::new().depth_spread(), None);
+        assert_eq!(DirectedAcyclicGraph::new().statistics().ref_spread, None);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_statistics_match_statistics() {
+        let mut dag = DirectedAcyclicGraph::new();
+        for node in 2..2000 {
+            dag.add_edge(node, node / 2);
+            dag.add_edge(node, node - 1);
+        }
+
+        assert_eq!(dag.par_statistics(), dag.statistics());
+    }
+}
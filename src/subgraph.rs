@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+use crate::hash::HashSet;
+use crate::DirectedAcyclicGraph;
+
+impl DirectedAcyclicGraph {
+    /// Induced subgraph on `nodes`, holding every edge between two of them
+    /// Attributes, weights and multiplicities are kept, nodes missing from the DAG are ignored
+    /// The origins inside `nodes` stay origins, if there are none every node
+    /// without out-references in the subgraph becomes an origin
+    /// # Arguments
+    /// * `nodes` - Node ids to keep
+    pub fn subgraph(&self, nodes: &HashSet<usize>) -> DirectedAcyclicGraph {
+        let kept: HashSet<usize> = nodes
+            .iter()
+            .copied()
+            .filter(|node| self.nodes.contains(node))
+            .collect();
+
+        let mut subgraph = DirectedAcyclicGraph::with_origins(self.origins.iter().copied());
+        for &node in &kept {
+            subgraph.nodes.insert(node);
+            if let Some(attributes) = self.attributes.get(&node) {
+                subgraph.attributes.insert(node, attributes.clone());
+            }
+            for &neighbor in self.neighbors(node) {
+                if kept.contains(&neighbor) {
+                    subgraph.insert_edge(node, neighbor);
+                    if let Some(weight) = self.weights.get(&(node, neighbor)) {
+                        subgraph.weights.insert((node, neighbor), *weight);
+                    }
+                    if let Some(multiplicity) = self.multiplicities.get(&(node, neighbor)) {
+                        subgraph
+                            .multiplicities
+                            .insert((node, neighbor), *multiplicity);
+                    }
+                }
+            }
+        }
+
+        let mut origins: Vec<usize> = self
+            .origins
+            .iter()
+            .copied()
+            .filter(|origin| kept.contains(origin))
+            .collect();
+        if origins.is_empty() {
+            origins = subgraph.roots().collect();
+            origins.sort_unstable();
+        }
+        if !origins.is_empty() {
+            subgraph.origins = origins;
+        }
+
+        subgraph
+    }
+
+    /// Subgraph of `node` and all of its ancestors, the cone between it and the origins
+    /// Empty if the node does not exist
+    /// # Arguments
+    /// * `node` - Node id at the bottom of the cone
+    pub fn ancestor_subgraph(&self, node: usize) -> DirectedAcyclicGraph {
+        let mut nodes = self.ancestors(node);
+        nodes.insert(node);
+        self.subgraph(&nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::HashSet;
+
+    use crate::sample_dag;
+
+    #[test]
+    fn test_subgraph() {
+        let mut dag = sample_dag();
+        dag.add_weighted_edge(6, 3, 2.0);
+
+        let cone = dag.ancestor_subgraph(6);
+        let mut edges: Vec<(usize, usize)> = cone.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, [(2, 1), (3, 1), (3, 2), (6, 3)]);
+        assert_eq!(cone.edge_weight(6, 3), Some(2.0));
+        assert_eq!(cone.origins(), &[1]);
+
+        let nodes: HashSet<usize> = [3, 5, 6, 42].iter().copied().collect();
+        let region = dag.subgraph(&nodes);
+        assert_eq!(region.nodes().count(), 3);
+        assert_eq!(region.edges().count(), 3);
+        assert_eq!(region.origins(), &[3]);
+        assert_eq!(region.max_depth(), 3);
+    }
+}
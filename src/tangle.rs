@@ -0,0 +1,213 @@
+use alloc::{vec, vec::Vec};
+
+use crate::hash::{HashMap, HashSet};
+use crate::{average, DirectedAcyclicGraph, Direction};
+
+impl DirectedAcyclicGraph {
+    /// Cumulative weight of `node` as used in tangle analysis,
+    /// the number of nodes referencing it directly or transitively
+    /// Returns 0 for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to weigh
+    pub fn cumulative_weight(&self, node: usize) -> usize {
+        self.descendant_count(node)
+    }
+
+    /// Cumulative weight of every node, see `cumulative_weight`
+    /// Computed in a single pass over the topological order, keeping one descendant bitset per node
+    /// Returns `None` if the graph is cyclic
+    pub fn cumulative_weights(&self) -> Option<HashMap<usize, usize>> {
+        let order = self.topological_order()?;
+        let positions: HashMap<usize, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(position, node)| (*node, position))
+            .collect();
+        let words = order.len().div_ceil(64);
+
+        // Referencing nodes come first in the order, so their sets are complete when needed
+        let mut descendants: Vec<Vec<u64>> = Vec::with_capacity(order.len());
+        for &node in &order {
+            let mut bits = vec![0u64; words];
+            for predecessor in self.predecessors(node) {
+                let predecessor = positions[predecessor];
+                bits[predecessor / 64] |= 1 << (predecessor % 64);
+                for (word, predecessor_word) in bits.iter_mut().zip(&descendants[predecessor]) {
+                    *word |= predecessor_word;
+                }
+            }
+            descendants.push(bits);
+        }
+
+        Some(
+            order
+                .iter()
+                .zip(&descendants)
+                .map(|(node, bits)| {
+                    let weight = bits.iter().map(|word| word.count_ones() as usize).sum();
+                    (*node, weight)
+                })
+                .collect(),
+        )
+    }
+
+    /// Average cumulative weight over all nodes
+    /// Returns `None` if the graph is empty or cyclic
+    pub fn avg_cumulative_weight(&self) -> Option<f64> {
+        let weights = self.cumulative_weights()?;
+        average(weights.values().sum::<usize>() as f64, weights.len())
+    }
+
+    /// Nodes neither among `tips` nor referenced by them directly or transitively, sorted ascending
+    /// In a tangle these are the transactions the current tips left behind
+    /// # Arguments
+    /// * `tips` - Node ids considered the current tips, e.g. a recent snapshot of `tips`
+    pub fn orphans(&self, tips: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        let mut confirmed = HashSet::new();
+        for tip in tips {
+            if !confirmed.contains(&tip) {
+                confirmed.extend(self.bfs(tip, Direction::Forward));
+            }
+        }
+
+        let mut orphans: Vec<usize> = self
+            .nodes()
+            .filter(|node| !confirmed.contains(node))
+            .collect();
+        orphans.sort_unstable();
+        orphans
+    }
+
+    /// Fraction of the nodes that are orphans with respect to `tips`, see `orphans`
+    /// Returns `None` for an empty DAG
+    /// # Arguments
+    /// * `tips` - Node ids considered the current tips
+    pub fn orphan_rate(&self, tips: impl IntoIterator<Item = usize>) -> Option<f64> {
+        average(self.orphans(tips).len() as f64, self.nodes.len())
+    }
+
+    /// Number of tips referencing `node` directly or transitively
+    /// A tip confirms itself
+    /// # Arguments
+    /// * `node` - Node id to look up
+    pub fn confirmation_count(&self, node: usize) -> usize {
+        self.bfs(node, Direction::Reverse)
+            .filter(|node| self.predecessors(*node).is_empty())
+            .count()
+    }
+
+    /// Confirmation count of every node, see `confirmation_count`
+    /// Walks forward once from every tip
+    pub fn confirmation_counts(&self) -> HashMap<usize, usize> {
+        let mut counts: HashMap<usize, usize> = self.nodes().map(|node| (node, 0)).collect();
+        for tip in self.tips() {
+            for node in self.bfs(tip, Direction::Forward) {
+                *counts.get_mut(&node).unwrap() += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Picks a tip with the weighted random walk of tangle tip selection
+    /// The walk starts at the first origin and repeatedly steps to a node referencing the current one,
+    /// choosing `y` with probability proportional to `exp(alpha * cumulative_weight(y))`
+    /// Returns `None` if the graph is cyclic or the origin is missing
+    /// # Arguments
+    /// * `alpha` - Bias towards heavy nodes, 0 gives an unbiased walk
+    /// * `rng` - Source of randomness
+    #[cfg(feature = "generator")]
+    pub fn select_tip<R: rand::Rng + ?Sized>(&self, alpha: f64, rng: &mut R) -> Option<usize> {
+        let weights = self.cumulative_weights()?;
+        self.walk_to_tip(&weights, alpha, rng)
+    }
+
+    /// Weighted random walk of `select_tip` with precomputed cumulative weights
+    /// # Arguments
+    /// * `weights` - Output of `cumulative_weights`
+    /// * `alpha` - Bias towards heavy nodes
+    /// * `rng` - Source of randomness
+    #[cfg(feature = "generator")]
+    pub(crate) fn walk_to_tip<R: rand::Rng + ?Sized>(
+        &self,
+        weights: &HashMap<usize, usize>,
+        alpha: f64,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let mut current = *self.origins().first()?;
+        if !self.nodes.contains(&current) {
+            return None;
+        }
+
+        loop {
+            let candidates = self.predecessors(current);
+            if candidates.is_empty() {
+                return Some(current);
+            }
+
+            // Shifted by the heaviest candidate so the exponentials can not overflow
+            let heaviest = candidates.iter().map(|node| weights[node]).max()?;
+            let probabilities: Vec<f64> = candidates
+                .iter()
+                .map(|node| (alpha * (weights[node] as f64 - heaviest as f64)).exp())
+                .collect();
+            let mut pick = rng.gen::<f64>() * probabilities.iter().sum::<f64>();
+            current = candidates[candidates.len() - 1];
+            for (node, probability) in candidates.iter().zip(&probabilities) {
+                if pick < *probability {
+                    current = *node;
+                    break;
+                }
+                pick -= probability;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sample_dag;
+
+    #[test]
+    fn test_cumulative_weights() {
+        let dag = sample_dag();
+        let weights = dag.cumulative_weights().unwrap();
+        for node in dag.nodes() {
+            assert_eq!(weights[&node], dag.cumulative_weight(node));
+        }
+        assert_eq!(weights[&1], 5);
+        assert_eq!(weights[&3], 2);
+        assert_eq!(dag.avg_cumulative_weight(), Some(2.0));
+    }
+
+    #[test]
+    fn test_orphans_and_confirmations() {
+        let dag = sample_dag();
+        assert!(dag.orphans(dag.tips()).is_empty());
+        assert_eq!(dag.orphans(vec![6]), [4, 5]);
+        assert_eq!(dag.orphan_rate(vec![6]), Some(2.0 / 6.0));
+
+        let counts = dag.confirmation_counts();
+        assert_eq!(counts[&1], 2);
+        assert_eq!(counts[&3], 1);
+        assert_eq!(counts[&4], 1);
+        for node in dag.nodes() {
+            assert_eq!(counts[&node], dag.confirmation_count(node));
+        }
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn test_select_tip() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let dag = crate::generator::uniform(300, &mut rng);
+        let tips: Vec<usize> = dag.tips().collect();
+        for alpha in &[0.0, 0.5, 100.0] {
+            let tip = dag.select_tip(*alpha, &mut rng).unwrap();
+            assert!(tips.contains(&tip));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Property based testing support, `proptest::arbitrary::Arbitrary` for `DirectedAcyclicGraph`
+//! Generated graphs are acyclic, node 1 is the origin and every node has a path to it
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::sample::Index;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::DirectedAcyclicGraph;
+
+/// Shape of the DAGs generated by `any_with::<DirectedAcyclicGraph>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DagParameters {
+    /// Largest number of nodes including the origin, at least 1
+    pub max_nodes: usize,
+    /// Largest number of parents per node, at least 1
+    pub max_parents: usize,
+}
+
+impl Default for DagParameters {
+    fn default() -> Self {
+        DagParameters {
+            max_nodes: 64,
+            max_parents: 3,
+        }
+    }
+}
+
+/// Node `i + 2` references between 1 and `max_parents` of the nodes before it,
+/// so shrinking removes nodes from the end and parents from every node
+impl Arbitrary for DirectedAcyclicGraph {
+    type Parameters = DagParameters;
+    type Strategy = BoxedStrategy<DirectedAcyclicGraph>;
+
+    fn arbitrary_with(parameters: DagParameters) -> Self::Strategy {
+        let parents = vec(
+            proptest::arbitrary::any::<Index>(),
+            1..=parameters.max_parents.max(1),
+        );
+        vec(parents, 0..parameters.max_nodes.max(1))
+            .prop_map(|nodes| {
+                let mut dag = DirectedAcyclicGraph::new();
+                dag.add_node(1);
+                for (offset, parents) in nodes.iter().enumerate() {
+                    let node = offset + 2;
+                    for parent in parents {
+                        dag.add_edge(node, parent.index(node - 1) + 1);
+                    }
+                }
+                dag
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::DagParameters;
+    use crate::{DagView, DirectedAcyclicGraph, Metric, RemovalPolicy};
+
+    proptest! {
+        #[test]
+        fn test_generated_graphs_are_rooted(dag in any::<DirectedAcyclicGraph>()) {
+            prop_assert!(dag.is_acyclic());
+            prop_assert!(dag.unreachable_from_origin().is_empty());
+            prop_assert_eq!(dag.origins(), &[1]);
+        }
+
+        #[test]
+        fn test_statistics_invariants(dag in any_with::<DirectedAcyclicGraph>(DagParameters {
+            max_nodes: 40,
+            max_parents: 4,
+        })) {
+            let statistics = dag.statistics();
+            prop_assert_eq!(statistics.values(), dag.metrics(&Metric::ALL));
+            prop_assert_eq!(&DagView::statistics(&dag.to_compact().unwrap()), &statistics);
+            prop_assert!(statistics.max_depth >= dag.level_sizes().len());
+            prop_assert_eq!(dag.level_sizes().iter().sum::<usize>(), dag.node_count());
+        }
+
+        #[test]
+        fn test_removal_invariants(
+            mut dag in any::<DirectedAcyclicGraph>(),
+            node in 2usize..64,
+        ) {
+            let nodes = dag.node_count();
+            match dag.remove_node_with(node, RemovalPolicy::RejectIfDisconnects) {
+                Ok(report) => prop_assert_eq!(dag.node_count(), nodes - report.nodes.len()),
+                Err(_) => prop_assert_eq!(dag.node_count(), nodes),
+            }
+            prop_assert!(dag.is_acyclic());
+            prop_assert!(dag.unreachable_from_origin().is_empty());
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{average, DirectedAcyclicGraph};
+
+impl DirectedAcyclicGraph {
+    /// Iterate over the tips, the nodes no other node references
+    pub fn tips(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes()
+            .filter(move |node| self.predecessors(*node).is_empty())
+    }
+
+    /// Iterate over the roots, the nodes that do not reference any other node
+    /// In a well formed DAG these are exactly the origins
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes()
+            .filter(move |node| self.neighbors(*node).is_empty())
+    }
+
+    /// Number of tips
+    pub fn tip_count(&self) -> usize {
+        self.tips().count()
+    }
+
+    /// Average shortest depth of the tips, see `avg_depth`
+    /// Tips without a path to an origin are skipped
+    /// Returns `None` if no tip has a path to an origin
+    pub fn avg_tip_depth(&self) -> Option<f64> {
+        let depths = self.min_depths();
+        let mut total = 0;
+        let mut count = 0;
+        for depth in self.tips().filter_map(|tip| depths.get(&tip)) {
+            total += depth;
+            count += 1;
+        }
+
+        average(total as f64, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sample_dag;
+
+    #[test]
+    fn test_tips_and_roots() {
+        let dag = sample_dag();
+
+        let mut tips: Vec<usize> = dag.tips().collect();
+        tips.sort_unstable();
+        assert_eq!(tips, [4, 5]);
+        assert_eq!(dag.roots().collect::<Vec<_>>(), [1]);
+        assert_eq!(dag.tip_count(), 2);
+        assert_eq!(dag.avg_tip_depth(), Some(2.0));
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+use crate::hash::HashSet;
+use crate::{detach, DirectedAcyclicGraph};
+
+impl DirectedAcyclicGraph {
+    /// Returns a copy of the DAG without the edges implied by a longer path, its Hasse diagram
+    /// Nodes, origins, attributes and the weights and multiplicities of kept edges are preserved
+    /// Cyclic graphs are copied unchanged since their reduction is not unique
+    pub fn transitive_reduction(&self) -> DirectedAcyclicGraph {
+        let redundant: HashSet<(usize, usize)> = self.redundant_edges().into_iter().collect();
+
+        let mut reduced = DirectedAcyclicGraph::with_origins(self.origins.iter().copied());
+        reduced.nodes = self.nodes.clone();
+        reduced.attributes = self.attributes.clone();
+        for (from, to) in self.edges().filter(|edge| !redundant.contains(edge)) {
+            match self.weights.get(&(from, to)) {
+                Some(weight) => reduced.add_weighted_edge(from, to, *weight),
+                None => reduced.add_edge(from, to),
+            };
+            if let Some(multiplicity) = self.multiplicities.get(&(from, to)) {
+                reduced.multiplicities.insert((from, to), *multiplicity);
+            }
+        }
+
+        reduced
+    }
+
+    /// Same as `transitive_reduction` but removes the redundant edges in place
+    /// Returns the number of edges that got removed
+    pub fn reduce_transitively(&mut self) -> usize {
+        let redundant = self.redundant_edges();
+        for (from, to) in &redundant {
+            detach(&mut self.neighbors, *from, *to);
+            detach(&mut self.predecessors, *to, *from);
+            self.weights.remove(&(*from, *to));
+            self.multiplicities.remove(&(*from, *to));
+        }
+
+        if !redundant.is_empty() {
+            self.edge_count -= redundant.len();
+            self.reachability = None;
+        }
+        redundant.len()
+    }
+
+    /// Returns a copy of the DAG with an edge from every node to each of its ancestors
+    /// Nodes, origins, attributes and the weights and multiplicities of existing edges
+    /// are preserved, added edges are unweighted and occur once
+    pub fn transitive_closure(&self) -> DirectedAcyclicGraph {
+        let mut closure = DirectedAcyclicGraph::with_origins(self.origins.iter().copied());
+        closure.nodes = self.nodes.clone();
+        closure.attributes = self.attributes.clone();
+        closure.weights = self.weights.clone();
+        closure.multiplicities = self.multiplicities.clone();
+        for node in self.nodes() {
+            for ancestor in self.ancestors(node) {
+                closure.add_edge(node, ancestor);
+            }
+        }
+
+        closure
+    }
+
+    /// Edges `from -> to` where `to` can also be reached through another neighbor of `from`
+    /// Removing them never isolates a node, both ends keep the longer path
+    fn redundant_edges(&self) -> Vec<(usize, usize)> {
+        if !self.is_acyclic() {
+            return Vec::new();
+        }
+
+        let mut redundant = Vec::new();
+        for node in self.nodes() {
+            let neighbors = self.neighbors(node);
+            if neighbors.len() < 2 {
+                continue;
+            }
+
+            // Everything reachable from a neighbor through at least one more edge
+            let mut reached = HashSet::new();
+            let mut stack: Vec<usize> = neighbors
+                .iter()
+                .flat_map(|neighbor| self.neighbors(*neighbor))
+                .copied()
+                .collect();
+            while let Some(next) = stack.pop() {
+                if reached.insert(next) {
+                    stack.extend(self.neighbors(next));
+                }
+            }
+
+            redundant.extend(
+                neighbors
+                    .iter()
+                    .filter(|&neighbor| reached.contains(neighbor))
+                    .map(|neighbor| (node, *neighbor)),
+            );
+        }
+
+        redundant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph};
+
+    #[test]
+    fn test_transitive_reduction() {
+        let mut dag = sample_dag();
+        let reduced = dag.transitive_reduction();
+
+        let mut edges: Vec<(usize, usize)> = reduced.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, [(2, 1), (3, 2), (4, 2), (5, 6), (6, 3)]);
+        assert_eq!(reduced.nodes().count(), 6);
+        assert_eq!(reduced.max_depth(), dag.max_depth());
+
+        assert_eq!(dag.reduce_transitively(), 2);
+        assert_eq!(dag.edges().count(), 5);
+        assert_eq!(dag.reduce_transitively(), 0);
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_weighted_edge(3, 2, 2.0);
+        dag.add_edge(4, 3);
+
+        let closure = dag.transitive_closure();
+        let mut edges: Vec<(usize, usize)> = closure.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, [(2, 1), (3, 1), (3, 2), (4, 1), (4, 2), (4, 3)]);
+        assert_eq!(closure.edge_weight(3, 2), Some(2.0));
+        assert_eq!(closure.transitive_reduction().edges().count(), 3);
+    }
+}
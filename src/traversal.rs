@@ -0,0 +1,210 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::hash::{HashMap, HashSet};
+use crate::DirectedAcyclicGraph;
+
+/// Direction edges are followed in during a traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// From a node to the nodes it references, towards the origins
+    Forward,
+    /// From a node to the nodes referencing it, towards the tips
+    Reverse,
+}
+
+impl DirectedAcyclicGraph {
+    /// Nodes adjacent to `node` in the given direction
+    /// # Arguments
+    /// * `node` - Node id to look up
+    /// * `direction` - Which edges to follow
+    fn adjacent(&self, node: usize, direction: Direction) -> &[usize] {
+        match direction {
+            Direction::Forward => self.neighbors(node),
+            Direction::Reverse => self.predecessors(node),
+        }
+    }
+
+    /// Breadth first traversal from `start`, yielding `start` first and every reached node once
+    /// Yields nothing if `start` does not exist
+    /// # Arguments
+    /// * `start` - Node id to start from
+    /// * `direction` - Which edges to follow
+    pub fn bfs(&self, start: usize, direction: Direction) -> Bfs<'_> {
+        let mut bfs = Bfs {
+            dag: self,
+            direction,
+            queue: VecDeque::new(),
+            visited: HashSet::new(),
+        };
+        if self.nodes.contains(&start) {
+            bfs.queue.push_back(start);
+            bfs.visited.insert(start);
+        }
+        bfs
+    }
+
+    /// Depth first traversal from `start` in pre-order, yielding `start` first and every reached node once
+    /// Yields nothing if `start` does not exist
+    /// # Arguments
+    /// * `start` - Node id to start from
+    /// * `direction` - Which edges to follow
+    pub fn dfs(&self, start: usize, direction: Direction) -> Dfs<'_> {
+        let mut dfs = Dfs {
+            dag: self,
+            direction,
+            stack: Vec::new(),
+            visited: HashSet::new(),
+        };
+        if self.nodes.contains(&start) {
+            dfs.stack.push(start);
+        }
+        dfs
+    }
+
+    /// Topological generations, starting with the nodes that reference nothing
+    /// Each generation holds every node whose referenced nodes (its parents) were all yielded before,
+    /// like networkx `topological_generations` with the edges pointing from parent to child
+    /// Nodes within a generation are sorted and never reference each other
+    /// Nodes on or behind a cycle are never yielded
+    pub fn generations(&self) -> Generations<'_> {
+        let remaining: HashMap<usize, usize> = self
+            .nodes()
+            .map(|node| (node, self.out_degree(node)))
+            .collect();
+        let mut current: Vec<usize> = self.roots().collect();
+        current.sort_unstable();
+
+        Generations {
+            dag: self,
+            remaining,
+            current,
+        }
+    }
+}
+
+/// Breadth first traversal iterator, created by `DirectedAcyclicGraph::bfs`
+pub struct Bfs<'a> {
+    dag: &'a DirectedAcyclicGraph,
+    direction: Direction,
+    queue: VecDeque<usize>,
+    /// Nodes that have been queued at some point
+    visited: HashSet<usize>,
+}
+
+impl Iterator for Bfs<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let node = self.queue.pop_front()?;
+        for &adjacent in self.dag.adjacent(node, self.direction) {
+            if self.visited.insert(adjacent) {
+                self.queue.push_back(adjacent);
+            }
+        }
+
+        Some(node)
+    }
+}
+
+/// Depth first traversal iterator, created by `DirectedAcyclicGraph::dfs`
+pub struct Dfs<'a> {
+    dag: &'a DirectedAcyclicGraph,
+    direction: Direction,
+    stack: Vec<usize>,
+    /// Nodes that have been yielded
+    visited: HashSet<usize>,
+}
+
+impl Iterator for Dfs<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(node) = self.stack.pop() {
+            if !self.visited.insert(node) {
+                continue;
+            }
+
+            // Pushed in reverse so the first adjacent node is explored first
+            let visited = &self.visited;
+            let adjacent = self.dag.adjacent(node, self.direction);
+            self.stack.extend(
+                adjacent
+                    .iter()
+                    .rev()
+                    .filter(|&adjacent| !visited.contains(adjacent)),
+            );
+            return Some(node);
+        }
+
+        None
+    }
+}
+
+/// Iterator over topological generations, created by `DirectedAcyclicGraph::generations`
+pub struct Generations<'a> {
+    dag: &'a DirectedAcyclicGraph,
+    /// Number of referenced nodes that were not yielded yet, per node
+    remaining: HashMap<usize, usize>,
+    /// Generation yielded next
+    current: Vec<usize>,
+}
+
+impl Iterator for Generations<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.current.is_empty() {
+            return None;
+        }
+
+        let mut next = Vec::new();
+        for node in &self.current {
+            for predecessor in self.dag.predecessors(*node) {
+                let remaining = self.remaining.get_mut(predecessor).unwrap();
+                *remaining -= 1;
+                if *remaining == 0 {
+                    next.push(*predecessor);
+                }
+            }
+        }
+        next.sort_unstable();
+
+        Some(core::mem::replace(&mut self.current, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, Direction};
+
+    #[test]
+    fn test_traversals() {
+        let dag = sample_dag();
+
+        assert_eq!(
+            dag.bfs(5, Direction::Forward).collect::<Vec<_>>(),
+            [5, 3, 6, 1, 2]
+        );
+        assert_eq!(
+            dag.dfs(5, Direction::Forward).collect::<Vec<_>>(),
+            [5, 3, 1, 2, 6]
+        );
+
+        let mut descendants: Vec<usize> = dag.dfs(2, Direction::Reverse).collect();
+        descendants.sort_unstable();
+        assert_eq!(descendants, [2, 3, 4, 5, 6]);
+        assert_eq!(dag.bfs(42, Direction::Reverse).count(), 0);
+    }
+
+    #[test]
+    fn test_generations() {
+        let dag = sample_dag();
+        let generations: Vec<Vec<usize>> = dag.generations().collect();
+        assert_eq!(
+            generations,
+            [vec![1], vec![2], vec![3, 4], vec![6], vec![5]]
+        );
+        assert_eq!(generations.len(), dag.max_depth());
+    }
+}
@@ -0,0 +1,368 @@
+#[cfg(feature = "std")]
+use alloc::collections::BTreeSet;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{BufReader, Read};
+
+#[cfg(feature = "std")]
+use crate::database::{parse_field, DataLines};
+#[cfg(feature = "std")]
+use crate::edge_list::is_header;
+use crate::hash::HashMap;
+#[cfg(feature = "std")]
+use crate::import::resolve_origins;
+use crate::DirectedAcyclicGraph;
+#[cfg(feature = "std")]
+use crate::{decompress, DagParseError, DuplicateParents, ParseOptions};
+
+/// Structural problems found by `DirectedAcyclicGraph::validate`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Every cycle found, listed as the nodes along the cycle in edge order
+    pub cycles: Vec<Vec<usize>>,
+    /// Nodes that have no path to the origin, sorted ascending
+    pub unreachable: Vec<usize>,
+    /// Nodes with an edge pointing to themselves, sorted ascending
+    pub self_references: Vec<usize>,
+    /// Edges listed more than once in the input, sorted ascending
+    /// Only the input validators fill this, a graph stores every edge once
+    pub duplicate_edges: Vec<(usize, usize)>,
+    /// Set if the database header disagrees with the number of node lines
+    pub header_mismatch: Option<HeaderMismatch>,
+}
+
+/// Node count announced by a database header next to the number of node lines actually present
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderMismatch {
+    /// Node line count announced by the header
+    pub expected: usize,
+    /// Node line count found in the input
+    pub actual: usize,
+}
+
+impl ValidationReport {
+    /// Returns if no problems were found
+    pub fn is_valid(&self) -> bool {
+        self.cycles.is_empty()
+            && self.unreachable.is_empty()
+            && self.self_references.is_empty()
+            && self.duplicate_edges.is_empty()
+            && self.header_mismatch.is_none()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "CYCLES: {}", self.cycles.len())?;
+        for cycle in &self.cycles {
+            writeln!(f, "    {:?}", cycle)?;
+        }
+        writeln!(
+            f,
+            "UNREACHABLE: {} {:?}",
+            self.unreachable.len(),
+            self.unreachable
+        )?;
+        writeln!(
+            f,
+            "SELF REFERENCES: {} {:?}",
+            self.self_references.len(),
+            self.self_references
+        )?;
+        writeln!(
+            f,
+            "DUPLICATE EDGES: {} {:?}",
+            self.duplicate_edges.len(),
+            self.duplicate_edges
+        )?;
+        match self.header_mismatch {
+            Some(HeaderMismatch { expected, actual }) => writeln!(
+                f,
+                "HEADER: announces {} node lines, found {}",
+                expected, actual
+            )?,
+            None => writeln!(f, "HEADER: ok")?,
+        }
+        write!(f, "{}", if self.is_valid() { "VALID" } else { "INVALID" })
+    }
+}
+
+/// DFS visiting state of a node
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    /// The node is on the current DFS path
+    Active,
+    /// The node and everything reachable from it has been explored
+    Done,
+}
+
+impl DirectedAcyclicGraph {
+    /// Returns if the graph contains no cycles
+    pub fn is_acyclic(&self) -> bool {
+        self.topological_order().is_some()
+    }
+
+    /// Nodes that have no path to an origin, sorted ascending
+    /// The depth statistics skip these nodes
+    pub fn unreachable_from_origin(&self) -> Vec<usize> {
+        let reachable = self.min_depths();
+        let mut unreachable: Vec<usize> = self
+            .nodes()
+            .filter(|node| !reachable.contains_key(node))
+            .collect();
+        unreachable.sort_unstable();
+        unreachable
+    }
+
+    /// Checks the graph for cycles, nodes unreachable from the origin and self-references
+    /// `add_edge` refuses self-references, they are reported for completeness
+    pub fn validate(&self) -> ValidationReport {
+        let mut self_references: Vec<usize> = self
+            .nodes()
+            .filter(|node| self.neighbors(*node).contains(node))
+            .collect();
+        self_references.sort_unstable();
+
+        ValidationReport {
+            cycles: self.cycles(),
+            unreachable: self.unreachable_from_origin(),
+            self_references,
+            duplicate_edges: Vec::new(),
+            header_mismatch: None,
+        }
+    }
+
+    /// Validates a database in the format read by `from_read`, also reporting what the parser
+    /// silently accepts: nodes listing themselves as a parent and a header that disagrees
+    /// with the number of node lines
+    /// Parents repeated on a node line are reported as duplicate edges, except for a node listing
+    /// the same parent on both sides, the single parent convention, unless duplicates are rejected
+    /// Fails only if the input can not be parsed at all
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    /// * `options` - How to parse the database, see `from_read_with`
+    #[cfg(feature = "std")]
+    pub fn validate_database(
+        reader: impl Read,
+        options: &ParseOptions,
+    ) -> Result<ValidationReport, DagParseError> {
+        let mut lines = DataLines::new(BufReader::new(decompress(reader)?));
+        let expected = lines.header()?;
+
+        let mut dag = DirectedAcyclicGraph::new();
+        let mut self_references = BTreeSet::new();
+        let mut duplicate_edges = BTreeSet::new();
+        let mut parents = Vec::new();
+        let mut node = 1;
+        while lines.next_line()? {
+            node += 1;
+
+            options.parse_parents(&lines.line, lines.number, &mut parents)?;
+            if parents.contains(&node) {
+                self_references.insert(node);
+            }
+
+            let single_parent =
+                parents.len() == 2 && options.duplicate_parent_policy() != DuplicateParents::Reject;
+            dag.nodes.insert(node);
+            for (i, &parent) in parents.iter().enumerate() {
+                dag.nodes.insert(parent);
+                if !parents[..i].contains(&parent) {
+                    dag.add_edge(node, parent);
+                } else if !single_parent {
+                    duplicate_edges.insert((node, parent));
+                }
+            }
+        }
+
+        let actual = node - 1;
+        let mut report = dag.validate();
+        report.self_references = self_references.into_iter().collect();
+        report.duplicate_edges = duplicate_edges.into_iter().collect();
+        if actual != expected {
+            report.header_mismatch = Some(HeaderMismatch { expected, actual });
+        }
+        Ok(report)
+    }
+
+    /// Validates a delimited edge list in the format read by `from_edge_list`,
+    /// reporting self-referencing and repeated edges instead of failing on them
+    /// Gzip and zstd compressed input is decompressed transparently, see `decompress`
+    /// Fails only if the input can not be parsed at all
+    /// # Arguments
+    /// * `reader` - Anything that implements `Read`
+    /// * `delimiter` - Field separator, e.g. `,` for CSV or `\t` for TSV
+    #[cfg(feature = "std")]
+    pub fn validate_edge_list(
+        reader: impl Read,
+        delimiter: char,
+    ) -> Result<ValidationReport, DagParseError> {
+        let mut lines = DataLines::new(BufReader::new(decompress(reader)?));
+
+        let mut dag = DirectedAcyclicGraph::new();
+        let mut self_references = BTreeSet::new();
+        let mut duplicate_edges = BTreeSet::new();
+        let mut first = true;
+        while lines.next_line()? {
+            let number = lines.number;
+            let mut fields = lines.line.split(delimiter).map(str::trim);
+            let from = fields.next().filter(|field| !field.is_empty());
+            let to = fields.next();
+            if core::mem::take(&mut first) && is_header(from, to) {
+                continue;
+            }
+
+            let from = parse_field(from, number, "source")?;
+            let to = parse_field(to, number, "target")?;
+            if from == to {
+                dag.nodes.insert(from);
+                self_references.insert(from);
+            } else if !dag.add_edge(from, to) {
+                duplicate_edges.insert((from, to));
+            }
+        }
+
+        resolve_origins(&mut dag);
+        let mut report = dag.validate();
+        report.self_references = self_references.into_iter().collect();
+        report.duplicate_edges = duplicate_edges.into_iter().collect();
+        Ok(report)
+    }
+
+    /// Finds the cycles closed by every back edge of a depth first search
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut roots: Vec<usize> = self.nodes().collect();
+        roots.sort_unstable();
+
+        let mut visits = HashMap::new();
+        let mut cycles = Vec::new();
+        for root in roots {
+            if visits.contains_key(&root) {
+                continue;
+            }
+
+            let mut path = vec![root];
+            let mut next_neighbor = vec![0];
+            visits.insert(root, Visit::Active);
+            while let Some(index) = next_neighbor.last_mut() {
+                let current = path[path.len() - 1];
+                match self.neighbors(current).get(*index).copied() {
+                    Some(neighbor) => {
+                        *index += 1;
+                        match visits.get(&neighbor) {
+                            None => {
+                                visits.insert(neighbor, Visit::Active);
+                                path.push(neighbor);
+                                next_neighbor.push(0);
+                            }
+                            Some(Visit::Active) => {
+                                let start = path.iter().position(|n| *n == neighbor).unwrap();
+                                cycles.push(path[start..].to_vec());
+                            }
+                            Some(Visit::Done) => {}
+                        }
+                    }
+                    None => {
+                        visits.insert(current, Visit::Done);
+                        path.pop();
+                        next_neighbor.pop();
+                    }
+                }
+            }
+        }
+
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sample_dag, DirectedAcyclicGraph, DuplicateParents, HeaderMismatch, ParseOptions};
+
+    #[test]
+    fn test_validate_reports_cycles_and_unreachable_nodes() {
+        let mut dag = sample_dag();
+        assert!(dag.is_acyclic());
+        assert!(dag.validate().is_valid());
+
+        dag.add_edge(3, 5);
+        dag.add_edge(8, 7);
+        let report = dag.validate();
+
+        assert!(!dag.is_acyclic());
+        assert_eq!(report.cycles.len(), 2);
+        for cycle in &report.cycles {
+            assert!(cycle.contains(&3) && cycle.contains(&5));
+        }
+        assert_eq!(report.unreachable, vec![7, 8]);
+        assert!(report.self_references.is_empty());
+    }
+
+    #[test]
+    fn test_validate_inputs() {
+        let database = "4\n1 1\n1 3\n2 3\n";
+        let report =
+            DirectedAcyclicGraph::validate_database(database.as_bytes(), &ParseOptions::new())
+                .unwrap();
+        assert_eq!(report.self_references, vec![3]);
+        assert_eq!(
+            report.header_mismatch,
+            Some(HeaderMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert!(report.duplicate_edges.is_empty());
+        assert!(!report.is_valid());
+        assert!(report.to_string().ends_with("INVALID"));
+
+        let database = "3\n1 1\n1 2 1\n2 3\n";
+        let relaxed = ParseOptions::new().relaxed(true);
+        let report =
+            DirectedAcyclicGraph::validate_database(database.as_bytes(), &relaxed).unwrap();
+        assert_eq!(report.duplicate_edges, vec![(3, 1)]);
+        assert!(!report.is_valid());
+
+        let reject = relaxed.duplicate_parents(DuplicateParents::Reject);
+        let report = DirectedAcyclicGraph::validate_database(database.as_bytes(), &reject).unwrap();
+        assert_eq!(report.duplicate_edges, vec![(2, 1), (3, 1)]);
+
+        let csv = "source,target\n2,1\n3,2\n2,1\n3,3\n";
+        let report = DirectedAcyclicGraph::validate_edge_list(csv.as_bytes(), ',').unwrap();
+        assert_eq!(report.duplicate_edges, vec![(2, 1)]);
+        assert_eq!(report.self_references, vec![3]);
+        assert!(report.cycles.is_empty());
+
+        #[cfg(feature = "flate2")]
+        {
+            use std::io::Write;
+
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(csv.as_bytes()).unwrap();
+            let gzip = encoder.finish().unwrap();
+            assert_eq!(
+                DirectedAcyclicGraph::validate_edge_list(gzip.as_slice(), ',').unwrap(),
+                report
+            );
+        }
+    }
+
+    #[test]
+    fn test_depth_statistics_skip_unreachable_nodes() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_edge(2, 1);
+        dag.add_edge(3, 2);
+        dag.add_edge(5, 4);
+
+        assert_eq!(dag.unreachable_from_origin(), vec![4, 5]);
+        assert_eq!(dag.avg_depth(), Some(1.0));
+        assert_eq!(dag.avg_tip_depth(), Some(2.0));
+
+        let statistics = dag.statistics();
+        assert_eq!(statistics.avg_depth, Some(1.0));
+        assert_eq!(statistics.avg_tip_depth, Some(2.0));
+        assert_eq!(statistics.unreachable_count, 2);
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::depth::{self, avg_paths_per_length};
+use crate::statistics;
+use crate::{average, DirectedAcyclicGraph, Statistics};
+
+/// Read-only structure of a DAG, implemented by `DirectedAcyclicGraph` and `CompactDag`
+/// The provided statistics only depend on the required methods, so they run on either
+/// representation and on any other implementation
+/// Edges point from a node to the parents it references, like in `DirectedAcyclicGraph`
+pub trait DagView {
+    /// Number of nodes
+    fn node_count(&self) -> usize;
+
+    /// Number of edges
+    fn edge_count(&self) -> usize;
+
+    /// Iterate over the node ids
+    fn node_ids(&self) -> impl Iterator<Item = usize> + '_;
+
+    /// Iterate over the nodes referenced by `node`, empty for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to look up
+    fn parent_ids(&self, node: usize) -> impl Iterator<Item = usize> + '_;
+
+    /// Iterate over the nodes referencing `node`, empty for missing nodes
+    /// # Arguments
+    /// * `node` - Node id to look up
+    fn child_ids(&self, node: usize) -> impl Iterator<Item = usize> + '_;
+
+    /// Iterate over the origins that are nodes of the graph
+    fn origin_ids(&self) -> impl Iterator<Item = usize> + '_;
+
+    /// Returns if `node` is one of the origins
+    /// # Arguments
+    /// * `node` - Node id to check
+    fn is_origin(&self, node: usize) -> bool;
+
+    /// Number of references to `node`, its in-degree counted by edge multiplicity
+    /// # Arguments
+    /// * `node` - Node id to count in-references of
+    fn in_references(&self, node: usize) -> usize;
+
+    /// Number of references made by `node`, its out-degree counted by edge multiplicity
+    /// # Arguments
+    /// * `node` - Node id to count out-references of
+    fn out_references(&self, node: usize) -> usize;
+
+    /// Number of references, the edges counted by their multiplicity
+    fn reference_count(&self) -> usize;
+
+    /// Orders the nodes so that every node comes before the nodes it references
+    /// Returns `None` if the graph contains a cycle
+    fn topological_order(&self) -> Option<Vec<usize>>;
+
+    /// Average shortest depth of the nodes with a path to an origin
+    /// Returns `None` if no node has a path to an origin
+    fn avg_depth(&self) -> Option<f64> {
+        let depths = depth::min_depths(self);
+        average(depths.values().sum::<usize>() as f64, depths.len())
+    }
+
+    /// Longest path to an origin counted in nodes
+    /// Returns 0 for cyclic graphs, unless the implementation can bound their paths
+    fn max_depth(&self) -> usize {
+        self.topological_order().map_or(0, |order| {
+            depth::max_depths(self, &order)
+                .values()
+                .max()
+                .map_or(0, |depth| depth + 1)
+        })
+    }
+
+    /// Average number of paths to an origin per path length, see
+    /// `DirectedAcyclicGraph::avg_node_per_depth`
+    /// Returns `None` if there are no paths besides the origins or the graph is cyclic
+    fn avg_node_per_depth(&self) -> Option<f64> {
+        let order = self.topological_order()?;
+        avg_paths_per_length(&depth::path_length_counts(self, &order))
+    }
+
+    /// Average in-reference per node
+    /// Returns `None` for an empty graph
+    fn avg_ref(&self) -> Option<f64> {
+        average(self.reference_count() as f64, self.node_count())
+    }
+
+    /// Average out-reference per node
+    /// Returns `None` for an empty graph
+    fn avg_out_ref(&self) -> Option<f64> {
+        average(self.reference_count() as f64, self.node_count())
+    }
+
+    /// Number of nodes at each shortest depth, indexed by depth so the origins are at 0
+    fn level_sizes(&self) -> Vec<usize> {
+        let mut sizes = Vec::new();
+        for depth in depth::min_depths(self).into_values() {
+            if sizes.len() <= depth {
+                sizes.resize(depth + 1, 0);
+            }
+            sizes[depth] += 1;
+        }
+        sizes
+    }
+
+    /// Largest number of nodes sharing the same shortest depth
+    fn width(&self) -> usize {
+        self.level_sizes().into_iter().max().unwrap_or(0)
+    }
+
+    /// Number of tips, the nodes no other node references
+    fn tip_count(&self) -> usize {
+        self.node_ids()
+            .filter(|node| self.child_ids(*node).next().is_none())
+            .count()
+    }
+
+    /// Average shortest depth of the tips with a path to an origin
+    /// Returns `None` if no tip has a path to an origin
+    fn avg_tip_depth(&self) -> Option<f64> {
+        let depths = depth::min_depths(self);
+        let tip_depths: Vec<usize> = self
+            .node_ids()
+            .filter(|node| self.child_ids(*node).next().is_none())
+            .filter_map(|tip| depths.get(&tip).copied())
+            .collect();
+        average(tip_depths.iter().sum::<usize>() as f64, tip_depths.len())
+    }
+
+    /// Edge count relative to the edges of a complete DAG on the same nodes
+    /// Returns `None` for fewer than two nodes
+    fn density(&self) -> Option<f64> {
+        let nodes = self.node_count();
+        if nodes < 2 {
+            return None;
+        }
+
+        Some(self.edge_count() as f64 / (nodes * (nodes - 1) / 2) as f64)
+    }
+
+    /// Average number of children of the nodes that have any
+    /// Returns `None` if no node is referenced
+    fn avg_branching_factor(&self) -> Option<f64> {
+        let referenced = self
+            .node_ids()
+            .filter(|node| self.child_ids(*node).next().is_some())
+            .count();
+        average(self.edge_count() as f64, referenced)
+    }
+
+    /// Share of the nodes that are tips
+    /// Returns `None` for an empty graph
+    fn leaf_fraction(&self) -> Option<f64> {
+        average(self.tip_count() as f64, self.node_count())
+    }
+
+    /// Computes every statistic at once, see `DirectedAcyclicGraph::statistics`
+    /// The path statistics of cyclic graphs are left at 0 and `None`,
+    /// unless the implementation can bound their paths
+    fn statistics(&self) -> Statistics {
+        let path_statistics = match self.topological_order() {
+            Some(order) => (
+                depth::max_depths(self, &order)
+                    .values()
+                    .max()
+                    .map_or(0, |depth| depth + 1),
+                depth::path_length_counts(self, &order),
+            ),
+            None => (0, BTreeMap::new()),
+        };
+
+        statistics::statistics_of(self, path_statistics)
+    }
+}
+
+/// Cyclic graphs fall back to enumerating their paths, like the inherent methods
+impl DagView for DirectedAcyclicGraph {
+    fn node_count(&self) -> usize {
+        DirectedAcyclicGraph::node_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        DirectedAcyclicGraph::edge_count(self)
+    }
+
+    fn node_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes()
+    }
+
+    fn parent_ids(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors(node).iter().copied()
+    }
+
+    fn child_ids(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.predecessors(node).iter().copied()
+    }
+
+    fn origin_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.origins()
+            .iter()
+            .copied()
+            .filter(move |origin| self.contains_node(*origin))
+    }
+
+    fn is_origin(&self, node: usize) -> bool {
+        DirectedAcyclicGraph::is_origin(self, node)
+    }
+
+    fn in_references(&self, node: usize) -> usize {
+        DirectedAcyclicGraph::in_references(self, node)
+    }
+
+    fn out_references(&self, node: usize) -> usize {
+        DirectedAcyclicGraph::out_references(self, node)
+    }
+
+    fn reference_count(&self) -> usize {
+        DirectedAcyclicGraph::reference_count(self)
+    }
+
+    fn topological_order(&self) -> Option<Vec<usize>> {
+        DirectedAcyclicGraph::topological_order(self)
+    }
+
+    fn max_depth(&self) -> usize {
+        DirectedAcyclicGraph::max_depth(self)
+    }
+
+    fn avg_node_per_depth(&self) -> Option<f64> {
+        DirectedAcyclicGraph::avg_node_per_depth(self)
+    }
+
+    fn avg_branching_factor(&self) -> Option<f64> {
+        DirectedAcyclicGraph::avg_branching_factor(self)
+    }
+
+    fn statistics(&self) -> Statistics {
+        DirectedAcyclicGraph::statistics(self)
+    }
+}
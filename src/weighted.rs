@@ -0,0 +1,112 @@
+use alloc::{vec, vec::Vec};
+
+use crate::hash::HashMap;
+use crate::DirectedAcyclicGraph;
+
+impl DirectedAcyclicGraph {
+    /// Average weighted depth of the nodes that can reach the origin
+    /// The weighted depth of a node is the lightest total edge weight of its paths to an origin
+    /// Returns `None` if the graph is cyclic or no node reaches the origin
+    pub fn weighted_avg_depth(&self) -> Option<f64> {
+        let depths = self.weighted_depths(|current, candidate| candidate < current)?;
+        if depths.is_empty() {
+            return None;
+        }
+
+        let total: f64 = depths.values().map(|(depth, _)| depth).sum();
+        Some(total / depths.len() as f64)
+    }
+
+    /// Heaviest total edge weight of any path to the origin
+    /// Returns `None` if the graph is cyclic or no node reaches the origin
+    pub fn weighted_max_depth(&self) -> Option<f64> {
+        let depths = self.weighted_depths(|current, candidate| candidate > current)?;
+        depths
+            .values()
+            .map(|(depth, _)| *depth)
+            .fold(None, |max: Option<f64>, depth| {
+                Some(max.map_or(depth, |max| max.max(depth)))
+            })
+    }
+
+    /// The heaviest path to the origin, starting at the origin like the paths of `depths`
+    /// Returns `None` if the graph is cyclic or no node reaches the origin
+    pub fn critical_path(&self) -> Option<Vec<usize>> {
+        let depths = self.weighted_depths(|current, candidate| candidate > current)?;
+        let mut node = depths
+            .iter()
+            .max_by(|(a, (a_depth, _)), (b, (b_depth, _))| {
+                a_depth.total_cmp(b_depth).then(b.cmp(a))
+            })
+            .map(|(node, _)| *node)?;
+
+        let mut path = vec![node];
+        while let Some(next) = depths[&node].1 {
+            path.push(next);
+            node = next;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Weighted depth of every node that can reach an origin, alongside the next node on the chosen path
+    /// Returns `None` if the graph is cyclic
+    /// # Arguments
+    /// * `prefer` - Returns if the candidate depth should replace the current one
+    fn weighted_depths(
+        &self,
+        prefer: impl Fn(f64, f64) -> bool,
+    ) -> Option<HashMap<usize, (f64, Option<usize>)>> {
+        let order = self.topological_order()?;
+
+        let mut depths: HashMap<usize, (f64, Option<usize>)> = HashMap::new();
+        for &node in order.iter().rev() {
+            if self.is_origin(node) {
+                depths.insert(node, (0.0, None));
+                continue;
+            }
+
+            let mut best: Option<(f64, Option<usize>)> = None;
+            for &neighbor in self.neighbors(node) {
+                if let Some((depth, _)) = depths.get(&neighbor) {
+                    let candidate = depth + self.edge_weight(node, neighbor).unwrap();
+                    if best.is_none_or(|(current, _)| prefer(current, candidate)) {
+                        best = Some((candidate, Some(neighbor)));
+                    }
+                }
+            }
+            if let Some(best) = best {
+                depths.insert(node, best);
+            }
+        }
+
+        Some(depths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirectedAcyclicGraph;
+
+    #[test]
+    fn test_weighted_statistics() {
+        let mut dag = DirectedAcyclicGraph::new();
+        dag.add_weighted_edge(2, 1, 5.0);
+        dag.add_weighted_edge(3, 1, 1.0);
+        dag.add_weighted_edge(4, 2, 2.0);
+        dag.add_weighted_edge(4, 3, 0.5);
+        dag.add_edge(5, 4);
+
+        assert_eq!(dag.edge_weight(5, 4), Some(1.0));
+        assert_eq!(dag.edge_weight(4, 5), None);
+
+        // depths: 1 -> 0, 2 -> 5, 3 -> 1, 4 -> 1.5, 5 -> 2.5
+        assert!((dag.weighted_avg_depth().unwrap() - 10.0 / 5.0).abs() < f64::EPSILON);
+        assert_eq!(dag.weighted_max_depth(), Some(8.0));
+        assert_eq!(dag.critical_path(), Some(vec![1, 2, 4, 5]));
+
+        dag.add_edge(1, 5);
+        assert_eq!(dag.critical_path(), None);
+    }
+}